@@ -7,11 +7,13 @@ use ink::{self};
 use ink::primitives::AccountId;
 use ink::storage::traits::StorageLayout;
 use scale::{Decode, Encode};
-use ink::prelude::{vec::Vec,vec,string::String};
+use ink::prelude::{vec::Vec,vec,string::String,collections::BTreeMap};
 
 use core::hash::Hash;
 use ink_types::Timestamp;
 
+pub type Balance = <ink::env::DefaultEnvironment as ink::env::Environment>::Balance;
+
 //------- The contracts has 5 parts --------------------------------//
 
 
@@ -28,7 +30,25 @@ use ink_types::Timestamp;
 
 
 /// Constants
-const MAX_KEYS:u8 = 3;
+/// Default number of allowed keys per team
+const DEFAULT_MAX_KEYS:u8 = 3;
+/// Default platform wide ceiling of allowed keys per team
+const DEFAULT_KEY_CEILING:u8 = 10;
+/// Default time before a team invitation expires, 7 days in milliseconds
+const DEFAULT_INVITATION_TTL: Timestamp = 604_800_000;
+/// Default time other allowed keys have to veto an admin handover, 2 days in milliseconds
+const DEFAULT_HANDOVER_DELAY: Timestamp = 172_800_000;
+/// Time a recovery request stays executable after its delay, 7 days in milliseconds
+const RECOVERY_REQUEST_TTL: Timestamp = 604_800_000;
+/// Owner of a contract upgraded from code without an owner, the account running the upgrade.
+/// Has to be set before building the upgrade, nobody can act as owner while it is all zeros.
+const UPGRADE_OWNER: [u8;32] = [0;32];
+/// Highest score a reviewer can give per rubric criterion
+pub const MAX_SCORE:u8 = 10;
+/// Longest reason in bytes a reviewer can give for a conflict of interest
+pub const MAX_REASON_LEN:u32 = 256;
+/// Most conflicts that can be recorded per application
+pub const MAX_CONFLICTS:u32 = 32;
 
 
 // Enums & Structs
@@ -75,6 +95,7 @@ pub enum Chains {
 
 /// Application profile , this consist of `application_id`, `applicant_name`, `issuer_id / name`
 /// and `reference of the application profile file`
+/// The `applicant` is the team's key pointer or the individual's account
 #[derive(Clone,Encode, Decode, Debug)]
 #[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
 pub struct Application{
@@ -82,6 +103,140 @@ pub struct Application{
     pub team_name: String,
     pub issuer_id: u16,
     pub issuer_name: String,
+    pub applicant: AccountId,
+    pub proposal: u8,
+    pub program: Option<u32>,
+    pub file: String,
+    pub status: Status,
+    pub feedback: Option<String>,
+    pub submitted: Timestamp,
+    pub updated: Timestamp,
+}
+
+/// Lifecycle of a proposal and of its active application
+/// ```text
+/// Draft -> Submitted -> UnderReview -> Approved -> Funded -> Completed
+///              ^              |
+///              |              -> ChangesRequested -> Submitted
+///              |              -> Rejected -> Submitted (new application)
+/// ```
+/// Any proposal which is not completed or rejected can be abandoned by the applicant.
+/// Only the issuer moves a proposal through review and funding, so only approved proposals get funded.
+/// The issuer cancels an approved or funded grant by rejecting it.
+#[derive(Eq,PartialEq,Encode,Decode,Clone,Debug,Default)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub enum Status {
+    #[default]
+    Draft,
+    Submitted,
+    UnderReview,
+    ChangesRequested,
+    Approved,
+    Rejected,
+    Funded,
+    Completed,
+    Abandoned
+}
+
+/// The side requesting a status change
+#[derive(Eq,PartialEq,Encode,Decode,Clone,Debug)]
+#[cfg_attr(feature = "std",derive(scale_info::TypeInfo))]
+pub enum Actor {
+    Applicant,
+    Issuer
+}
+
+impl Status {
+    pub fn can_transition(&self,to:&Status,actor:&Actor) -> bool {
+        use Status::*;
+        match actor {
+            Actor::Applicant => matches!(
+                (self,to),
+                (Draft,Submitted) |
+                (ChangesRequested,Submitted) |
+                (Rejected,Submitted)
+            ) || (*to == Abandoned && !matches!(self,Completed | Rejected | Abandoned)),
+            Actor::Issuer => matches!(
+                (self,to),
+                (Submitted,UnderReview) |
+                (UnderReview,ChangesRequested) |
+                (UnderReview,Approved) |
+                (UnderReview,Rejected) |
+                (Approved,Rejected) |
+                (Approved,Funded) |
+                (Funded,Rejected) |
+                (Funded,Completed)
+            )
+        }
+    }
+}
+
+
+/// Weighted criterion of an issuer's review rubric
+#[derive(Clone,Encode, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct Criterion {
+    pub name: String,
+    pub weight: u8
+}
+
+/// Scores of a reviewer, one per rubric criterion from 0 to [MAX_SCORE]
+/// `comments` is the reference of the comments file
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct Review {
+    pub reviewer: AccountId,
+    pub scores: Vec<u8>,
+    pub comments: String,
+    pub submitted: Timestamp
+}
+
+/// Conflict of interest declared by a reviewer on an application
+/// `recused` is set once the reviewer steps down from the review
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct Conflict {
+    pub reviewer: AccountId,
+    pub reason: String,
+    pub declared: Timestamp,
+    pub recused: bool
+}
+
+/// Final decision of the issuer, `score` is the aggregate score times 100
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct Decision {
+    pub status: Status,
+    pub score: Option<u32>,
+    pub time: Timestamp
+}
+
+/// Review of an application by the assigned reviewers following the rubric at assignment time
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct ReviewRound {
+    pub reviewers: Vec<AccountId>,
+    pub rubric: Vec<Criterion>,
+    pub reviews: Vec<Review>,
+    pub decision: Option<Decision>
+}
+
+impl ReviewRound {
+    /// Weighted average of all submitted reviews times 100, `None` if there are no reviews
+    pub fn aggregate_score(&self) -> Option<u32> {
+        let total_weight: u32 = self.rubric.iter().map(|criterion| criterion.weight as u32).sum();
+        if self.reviews.is_empty() || total_weight == 0 {
+            return None
+        }
+
+        let weighted: u32 = self.reviews.iter().map(|review|{
+            review.scores.iter().zip(self.rubric.iter())
+                .map(|(score,criterion)| *score as u32 * criterion.weight as u32)
+                .sum::<u32>()
+        }).sum();
+
+        Some(weighted.saturating_mul(100) / (total_weight * self.reviews.len() as u32))
+    }
 }
 
 
@@ -96,7 +251,79 @@ pub enum Error {
     ProfileDontExists,
     MaxKeysExceeded,
     AccountExistsOrMaxExceeded,
+    /// Caller manages several teams and has to specify which one
+    TeamNotSpecified,
+    RoleNotFound,
+    /// The team can't define more roles
+    MaxRolesExceeded,
+    /// Key capabilities and the admin role are not granted through roles
+    InvalidRole,
+    InvitationNotFound,
+    InvitationExpired,
+    /// The team must keep at least one admin
+    LastAdmin,
+    /// The key admin has to be changed before leaving the team
+    AdminHandoverRequired,
+    /// The account is still an allowed key of a team and has to be removed from it first
+    TeamKeysHeld,
+    // Admin handover and recovery errors
+    HandoverNotFound,
+    /// No recovery request or it has lapsed
+    RecoveryNotFound,
+    /// Another recovery request is still pending
+    RecoveryPending,
+    /// The waiting period has not passed yet
+    TimeLocked,
+    // Multi-key approval errors
+    OperationNotFound,
+    AlreadyApproved,
+    /// Threshold is zero or higher than the number of allowed keys
+    InvalidThreshold,
+    // Grant program errors
+    ProgramNotFound,
+    /// The program is closed or the current time is outside its open and close window
+    ProgramClosed,
+    /// The close time is not after the open time or has already passed
+    InvalidSchedule,
+    /// The proposal's chain, ask or storage does not match the program
+    NotEligible,
+    /// An attached project already uses more storage than the quota
+    QuotaBelowUsage,
+    ProposalNotFound,
+    ProposalAlreadyAttached,
     // Grant Application errors
+    ApplicationNotFound,
+    /// The proposal can not move from its current status to the requested one
+    InvalidTransition,
+    // Review errors
+    RubricNotFound,
+    /// The rubric is empty or has a criterion without weight
+    InvalidRubric,
+    /// The scores don't match the rubric or exceed [MAX_SCORE]
+    InvalidScores,
+    NotReviewer,
+    /// The reviewer is on the applicant team or recused from the application
+    ConflictOfInterest,
+    /// Reviews are only visible to the applicant once the decision is published
+    ReviewsHidden,
+    /// The reason is longer than [MAX_REASON_LEN] bytes
+    ReasonTooLong,
+    /// The application already has [MAX_CONFLICTS] recorded conflicts
+    MaxConflictsExceeded,
+    // Escrow errors
+    /// Escrow is only deposited on approved or funded proposals
+    NotApproved,
+    /// No value was transferred with the deposit
+    InvalidAmount,
+    /// The project has no escrow or nothing is left in it
+    EscrowNotFound,
+    /// The proposal is not abandoned, rejected or completed
+    NotCancelled,
+    /// A project escrow still holds funds, the issuer has to reclaim them first
+    EscrowNotSettled,
+    TransferFailed,
+    /// A queued proposal or milestone operation failed with the inner error
+    Milestone(MilestoneError),
     /// Any system related error
     UnexpectedError,
     SecretKeyNotAuthorized,
@@ -111,26 +338,107 @@ pub enum MilestoneError {
     UnexpectedError,
     StorageExceeded,
     MilestoneNotFound,
-    ProjectNotFound
+    ProjectNotFound,
+    /// Pivoting requires a reason
+    PivotReasonRequired,
+    /// The milestone's delivery state does not allow the action
+    InvalidState,
+    /// Deliveries can only be submitted on approved or funded proposals
+    NotApproved,
+    /// The main milestone was replaced by a pivot, only pivoting again changes it
+    MilestoneReplaced,
+    /// Project ids are `u8`, an owner can not have more than 255 projects
+    MaxProjectsExceeded,
+    /// Edit numbers are `u8`, a milestone can not be edited more than 255 times
+    MaxEditsExceeded,
+    DeliverableNotFound,
+    /// The project's escrow does not cover the milestone's amount
+    InsufficientEscrow,
+    /// The payouts would exceed the amount asked for the project in its program
+    AskExceeded,
+    TransferFailed,
+    /// Milestone numbers are `u8`, a project's active path can not have more than 255 milestones
+    MaxMilestonesExceeded
 }
 
 
 
 /// Team Member Roles
+/// `Custom` points to a role defined by the team
 #[derive(Clone, Encode, Decode, Debug,PartialEq)]
 #[cfg_attr(feature = "std", derive(StorageLayout, scale_info::TypeInfo))]
 pub enum MemberRole {
     Admin,
-    Regular
+    Regular,
+    Custom(u8)
 }
 
-#[derive(Clone,Encode, Decode, Debug)]
+/// Actions a team member can be allowed to perform
+/// `ManageKeys` is held by the key admin and `ApproveOperations` by the allowed keys,
+/// these two can not be granted through custom roles.
+#[derive(Clone, Encode, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std", derive(StorageLayout, scale_info::TypeInfo))]
+pub enum Capability {
+    EditProfile,
+    SubmitProposal,
+    EditMilestone,
+    ManageMembers,
+    ViewPrivateData,
+    ManageKeys,
+    ApproveOperations
+}
+
+/// Capabilities of the `Regular` role, `Admin` has all the capabilities
+pub const REGULAR_CAPABILITIES: [Capability; 2] = [Capability::ViewPrivateData, Capability::EditMilestone];
+
+/// Pending invitation of an account to join a team
+#[derive(Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(StorageLayout, scale_info::TypeInfo))]
+pub struct Invitation {
+    pub team: AccountId,
+    pub role: MemberRole,
+    pub invited_by: AccountId,
+    pub expires: Timestamp
+}
+
+/// Role defined by a team with its own set of capabilities
+#[derive(Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(StorageLayout, scale_info::TypeInfo))]
+pub struct TeamRole {
+    pub id: u8,
+    pub name: String,
+    pub capabilities: Vec<Capability>
+}
+
+#[derive(Clone,Encode, Decode, Debug,PartialEq)]
 #[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
 pub enum UserRole {
     Applicant,
     Issuer
 }
 
+/// What happens to the proposals of a deleted profile
+/// `Archive` keeps the proposals as they are, `Tombstone` keeps only the proposal ids and chain references
+#[derive(Clone,Encode, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub enum ErasurePolicy {
+    Archive,
+    Tombstone
+}
+
+/// Profile fields which can be updated after creation
+#[derive(Clone,Encode, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub enum ProfileField {
+    Description,
+    Categories,
+    Chains,
+    Links,
+    Role,
+    Name,
+    Mission
+}
+
 
 #[derive(Clone,Encode, Decode, Debug)]
 #[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
@@ -178,10 +486,65 @@ impl IndividualProfile {
         Ok(())
     }
 
+    pub fn remove_ref_team(&mut self,id:AccountId){
+        self.ref_team.retain(|team| team.0 != id);
+    }
+
+    pub fn update_ref_team_role(&mut self,id:AccountId,role:MemberRole){
+        if let Some(team) = self.ref_team.iter_mut().find(|team| team.0 == id){
+            team.1 = role;
+        }
+    }
+
     pub fn update_applications_count(&mut self){
         self.applications += 1
     }
 
+    /// Applies the provided fields and returns the ones whose value changed
+    pub fn update_profile(
+        &mut self,
+        description: Option<String>,
+        categories: Option<Vec<Categories>>,
+        chains: Option<Vec<Chains>>,
+        links: Option<Vec<String>>,
+        role: Option<UserRole>
+    ) -> Vec<ProfileField> {
+        let mut changed = vec![];
+
+        if let Some(description) = description {
+            if self.description != description {
+                self.description = description;
+                changed.push(ProfileField::Description);
+            }
+        }
+        if let Some(categories) = categories {
+            if self.categories != categories {
+                self.categories = categories;
+                changed.push(ProfileField::Categories);
+            }
+        }
+        if let Some(chains) = chains {
+            if self.chains != chains {
+                self.chains = chains;
+                changed.push(ProfileField::Chains);
+            }
+        }
+        if let Some(links) = links {
+            if self.links != links {
+                self.links = links;
+                changed.push(ProfileField::Links);
+            }
+        }
+        if let Some(role) = role {
+            if self.role != role {
+                self.role = role;
+                changed.push(ProfileField::Role);
+            }
+        }
+
+        changed
+    }
+
 }
 
 
@@ -238,23 +601,200 @@ impl TeamApplicantProfile {
         self.applications += 1
     }
 
-    
+    pub fn remove_member(&mut self,id:AccountId){
+        self.members.retain(|mem| mem.0 != id);
+    }
+
+    pub fn add_member(&mut self,id:AccountId,role:MemberRole) -> CreateResult<()>{
+        if self.members.iter().any(|mem| mem.0 == id){
+            return Err(Error::AccountExists)
+        }
+        self.members.push((id,role));
+        Ok(())
+    }
+
+    pub fn member_role(&self,id:AccountId) -> Option<MemberRole>{
+        self.members.iter().find(|mem| mem.0 == id).map(|mem| mem.1.clone())
+    }
+
+    pub fn update_member_role(&mut self,id:AccountId,role:MemberRole) -> CreateResult<()>{
+        let member = self.members.iter_mut().find(|mem| mem.0 == id).ok_or(Error::AccountDontExists)?;
+        member.1 = role;
+        Ok(())
+    }
+
+    /// Applies the provided fields and returns the ones whose value changed
+    pub fn update_profile(
+        &mut self,
+        name: Option<String>,
+        description: Option<String>,
+        mission: Option<String>,
+        categories: Option<Vec<Categories>>,
+        chain: Option<Vec<Chains>>,
+        links: Option<Vec<String>>,
+    ) -> Vec<ProfileField> {
+        let mut changed = vec![];
+
+        if let Some(name) = name {
+            if self.name != name {
+                self.name = name;
+                changed.push(ProfileField::Name);
+            }
+        }
+        if let Some(description) = description {
+            if self.description != description {
+                self.description = description;
+                changed.push(ProfileField::Description);
+            }
+        }
+        if let Some(mission) = mission {
+            if self.mission != mission {
+                self.mission = mission;
+                changed.push(ProfileField::Mission);
+            }
+        }
+        if let Some(categories) = categories {
+            if self.categories != categories {
+                self.categories = categories;
+                changed.push(ProfileField::Categories);
+            }
+        }
+        if let Some(chain) = chain {
+            if self.chain != chain {
+                self.chain = chain;
+                changed.push(ProfileField::Chains);
+            }
+        }
+        if let Some(links) = links {
+            if self.links != links {
+                self.links = links;
+                changed.push(ProfileField::Links);
+            }
+        }
+
+        changed
+    }
+
+}
+
+
+
+
+/// Grant level offered by an issuer based on amount, e.g `("Level 1", 10_000)`
+#[derive(Clone,Encode, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct GrantLevel {
+    pub name: String,
+    pub amount: Balance
+}
+
+
+/// A grant issuer profile
+/// The issuer is controlled by the allowed keys of its `keys`
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct IssuerProfile {
+    id: u16,
+    name: String,
+    description: String,
+    chain: Vec<Chains>,
+    categories: Vec<Categories>,
+    grant_levels: Vec<GrantLevel>,
+    requirements: Vec<String>,
+    registered_time: Timestamp,
+    keys: KeyManagement
+}
+
+impl IssuerProfile {
+    pub fn is_allowed(&self,account:&AccountId) -> bool {
+        self.keys.allowed_keys.contains(account)
+    }
+}
+
+
+/// A grant program published by an issuer
+/// Proposals can be attached while `open <= block_timestamp < close` and the program is not closed.
+/// `template` is the CID of the proposal template applicants are required to follow.
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct GrantProgram {
+    pub id: u32,
+    pub issuer_id: u16,
+    pub name: String,
+    pub open: Timestamp,
+    pub close: Timestamp,
+    pub budget: Balance,
+    pub categories: Vec<Categories>,
+    pub chains: Vec<Chains>,
+    pub max_ask: Balance,
+    pub template: String,
+    // Storage quota of the attached projects in bytes, [MAX_MEM] if not set
+    pub quota: Option<u32>,
+    pub closed: bool,
+    // (proposal owner, proposal id) attached to the program
+    pub proposals: Vec<(AccountId,u8)>
+}
+
+impl GrantProgram {
+    pub fn is_open(&self,now:Timestamp) -> bool {
+        !self.closed && self.open <= now && now < self.close
+    }
+}
+
+
+/// Funds an issuer deposited for the milestone payouts of a project
+/// Accepted milestones are paid from it, what is left can be reclaimed by the issuer after cancellation.
+#[derive(Clone,Encode, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct Escrow {
+    pub issuer_id: u16,
+    pub deposited: Balance,
+    pub released: Balance,
+    pub reclaimed: Balance
+}
 
+impl Escrow {
+    pub fn remaining(&self) -> Balance {
+        self.deposited.saturating_sub(self.released).saturating_sub(self.reclaimed)
+    }
 }
 
 
+/// Key management layout of the contract before the key index, kept to decode `manage_keys`
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
+pub struct LegacyKeyManagement{
+    admin: AccountId,
+    key_pointer: AccountId,
+    allowed_keys: Vec<AccountId>
+}
 
+impl From<LegacyKeyManagement> for KeyManagement {
+    fn from(legacy: LegacyKeyManagement) -> Self {
+        Self{
+            admin: legacy.admin,
+            key_pointer: legacy.key_pointer,
+            allowed_keys: legacy.allowed_keys,
+            threshold: 1,
+            max_keys: DEFAULT_MAX_KEYS,
+        }
+    }
+}
 
 /// Key management struct
 /// This will allow multiple members in certain organization to manage the account
 /// The allowed members will be granted by `admin` key
 /// The `key_pointer` is the key used in the key to `IssuerProfile` mapping
+/// The `threshold` is the number of allowed keys required to approve sensitive actions
+/// The `max_keys` is the team's limit of allowed keys, bounded by the platform ceiling
 #[derive(Clone,Encode,Hash, Decode, Debug)]
 #[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
 pub struct KeyManagement{
     admin: AccountId,
     key_pointer: AccountId, // Account Id for now
-    allowed_keys: Vec<AccountId>
+    allowed_keys: Vec<AccountId>,
+    threshold: u8,
+    max_keys: u8
 }
 
 #[derive(Clone,Encode, Decode, Debug)]
@@ -265,19 +805,169 @@ pub enum KeyAction{
     ChangeAdmin
 }
 
+/// Sensitive team actions which require `threshold` approvals from the allowed keys
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub enum TeamOperation {
+    SubmitProposal {
+        chain: Chains,
+        ref_no: Option<u32>,
+        file: String,
+        mem: u32
+    },
+    PivotMilestone {
+        project: u8,
+        mile_no: u8,
+        reason: String,
+        file: String,
+        mem: u32,
+        terms: MilestoneTerms
+    },
+    ChangeAdmin(AccountId),
+    RemoveKey(AccountId),
+    RemoveMember(AccountId),
+    SetThreshold(u8),
+    SetRecoveryConfig(RecoveryConfig),
+    AttachProposal {
+        project: u8,
+        program: u32,
+        ask: Balance
+    }
+}
+
+impl TeamOperation {
+    /// The account the operation takes out of the team, if any
+    pub fn removed_account(&self) -> Option<AccountId> {
+        match self {
+            TeamOperation::RemoveKey(account) | TeamOperation::RemoveMember(account) => Some(*account),
+            _ => None
+        }
+    }
+}
+
+/// Admin handover and social recovery settings of a team
+/// `handover_delay` is the time allowed keys have to veto an admin handover
+/// `guardians` can jointly appoint a new admin once `threshold` of them agree and `recovery_delay` has passed
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct RecoveryConfig {
+    pub handover_delay: Timestamp,
+    pub guardians: Vec<AccountId>,
+    pub threshold: u8,
+    pub recovery_delay: Timestamp
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            handover_delay: DEFAULT_HANDOVER_DELAY,
+            guardians: vec![],
+            threshold: 0,
+            recovery_delay: 0
+        }
+    }
+}
+
+impl RecoveryConfig {
+    pub fn validate(&self) -> CreateResult<()> {
+        if !self.guardians.is_empty() && (self.threshold == 0 || self.threshold as usize > self.guardians.len()) {
+            return Err(Error::InvalidThreshold)
+        }
+        Ok(())
+    }
+
+    /// Whether the `new` settings shorten the veto window or make recovering the team harder
+    pub fn weakened_by(&self,new:&RecoveryConfig) -> bool {
+        let has_guardians = !self.guardians.is_empty();
+        new.handover_delay < self.handover_delay ||
+            self.guardians.iter().any(|guardian| !new.guardians.contains(guardian)) ||
+            (has_guardians && new.threshold > self.threshold) ||
+            (has_guardians && new.recovery_delay > self.recovery_delay)
+    }
+}
+
+/// Admin handover waiting for the new admin to accept
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct AdminHandover {
+    pub new_admin: AccountId,
+    pub proposed_by: AccountId,
+    pub executable_at: Timestamp
+}
+
+/// New admin appointment by the guardians, it lapses [RECOVERY_REQUEST_TTL] after `executable_at`
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct RecoveryRequest {
+    pub new_admin: AccountId,
+    pub approvals: Vec<AccountId>,
+    pub executable_at: Timestamp,
+    pub expires: Timestamp
+}
+
+/// Approved team operation waiting out the handover delay, the other keys and the guardians can veto it
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct DelayedOperation {
+    pub id: u32,
+    pub proposer: AccountId,
+    pub operation: TeamOperation,
+    pub executable_at: Timestamp
+}
+
+/// Team operation waiting for enough approvals before being executed
+#[derive(Clone,Encode, Decode, Debug)]
+#[cfg_attr(feature = "std",derive(StorageLayout,scale_info::TypeInfo))]
+pub struct PendingOperation {
+    pub id: u32,
+    pub proposer: AccountId,
+    pub operation: TeamOperation,
+    pub approvals: Vec<AccountId>,
+    pub created: Timestamp
+}
+
 impl KeyManagement {
     pub fn new(admin: AccountId) ->CreateResult<()>{
         Self{
             admin,
             key_pointer: admin,
             allowed_keys: vec![admin],
+            threshold: 1,
+            max_keys: DEFAULT_MAX_KEYS,
         };
         Ok(())
     }
+
+    /// Number of keys which can still be added
+    pub fn remaining_keys(&self) -> u8 {
+        self.max_keys.saturating_sub(self.allowed_keys.len() as u8)
+    }
+
+    /// Sets the team's key limit, it can't be lower than the current keys nor higher than the `ceiling`
+    pub fn update_max_keys(&mut self, max_keys: u8, ceiling: u8) -> CreateResult<()> {
+        if max_keys > ceiling || (max_keys as usize) < self.allowed_keys.len() {
+            return Err(Error::MaxKeysExceeded)
+        }
+        self.max_keys = max_keys;
+        Ok(())
+    }
+    /// Number of `approvals` which are still allowed keys
+    pub fn approvals_count(&self, approvals: &[AccountId]) -> u8 {
+        approvals.iter().filter(|key| self.allowed_keys.contains(key)).count() as u8
+    }
+
+    pub fn update_threshold(&mut self, threshold: u8) -> CreateResult<()> {
+        if threshold == 0 || threshold as usize > self.allowed_keys.len() {
+            return Err(Error::InvalidThreshold)
+        }
+        self.threshold = threshold;
+        Ok(())
+    }
+
     pub fn update_keys_inner(&mut self, key: AccountId, action:KeyAction) -> CreateResult<()> {
        match action {
             KeyAction::ADD => {
-                if !self.allowed_keys.contains(&key) && self.allowed_keys.len() as u8 <= MAX_KEYS {
+                if !self.allowed_keys.contains(&key) && self.allowed_keys.len() < self.max_keys as usize {
                     self.allowed_keys.push(key);
                     Ok(())
                 }else{
@@ -285,6 +975,10 @@ impl KeyManagement {
                 }
             },
             KeyAction::REMOVE => {
+               // Removing the key must not leave the team unable to reach the threshold
+               if self.allowed_keys.len() <= self.threshold as usize {
+                   return Err(Error::InvalidThreshold)
+               }
                if let Some(index) = self.allowed_keys.iter().position(|k| *k == key){
                    self.allowed_keys.remove(index);
                    Ok(())
@@ -322,30 +1016,118 @@ impl EditedMile {
     }
 }
 
+#[derive(Encode,Clone,Default, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
+pub enum DeliverableStatus {
+    #[default]
+    Pending,
+    InProgress,
+    Done
+}
+
+#[derive(Encode,Clone,Default, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
+pub struct Deliverable {
+    pub description: String,
+    pub status: DeliverableStatus
+}
+
+/// Delivery state of a milestone
+/// `Planned -> Submitted -> Accepted | Rejected | ChangesRequested -> Submitted`
+#[derive(Encode,Clone,Default, Decode, Debug,PartialEq)]
+#[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
+pub enum MilestoneState {
+    #[default]
+    Planned,
+    Submitted,
+    ChangesRequested,
+    Accepted,
+    Rejected
+}
+
+/// Terms of a milestone, `due` is a block timestamp and `0` means no deadline
+/// `amount` is the requested payout and `deliverables` the descriptions of what will be delivered
+#[derive(Encode,Clone,Default, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
+pub struct MilestoneTerms {
+    pub due: Timestamp,
+    pub amount: Balance,
+    pub deliverables: Vec<String>
+}
+
 #[derive(Encode,Clone,Default, Decode, Debug)]
 #[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
 pub struct AddMilestone {
     pub main_index:u8,
     pub no_edits:u8,
     data: String,
-    mem: u32
+    mem: u32,
+    pub due: Timestamp,
+    pub amount: Balance,
+    pub deliverables: Vec<Deliverable>,
+    // Delivery evidence and the evaluator's comments, both references of the stored files
+    pub state: MilestoneState,
+    pub evidence: Option<String>,
+    pub comments: Option<String>
 }
 
 impl AddMilestone{
-    pub fn new(main_index:u8,no_edits:u8,data:String,mem:u32) -> Self{
+    pub fn new(main_index:u8,no_edits:u8,data:String,mem:u32,terms:MilestoneTerms) -> Self{
         Self{
             main_index,
             no_edits,
             data,
             mem,
+            due: terms.due,
+            amount: terms.amount,
+            deliverables: terms.deliverables.into_iter().map(|description| Deliverable{
+                description,
+                status: DeliverableStatus::Pending
+            }).collect(),
+            state: MilestoneState::Planned,
+            evidence: None,
+            comments: None,
+        }
+    }
+
+    /// Submits the delivery evidence, a milestone can be submitted again after changes were requested
+    pub fn submit(&mut self,evidence:String) -> Result<(),MilestoneError>{
+        if !matches!(self.state,MilestoneState::Planned | MilestoneState::ChangesRequested) {
+            return Err(MilestoneError::InvalidState)
+        }
+        self.state = MilestoneState::Submitted;
+        self.evidence = Some(evidence);
+        Ok(())
+    }
+
+    /// Accepts, rejects or requests changes on the submitted delivery
+    pub fn evaluate(&mut self,state:MilestoneState,comments:Option<String>) -> Result<(),MilestoneError>{
+        let decision = matches!(state,MilestoneState::Accepted | MilestoneState::Rejected | MilestoneState::ChangesRequested);
+        if !decision || self.state != MilestoneState::Submitted {
+            return Err(MilestoneError::InvalidState)
         }
+        self.state = state;
+        self.comments = comments;
+        Ok(())
+    }
+
+    /// The delivery is accepted or all deliverables are done
+    pub fn is_complete(&self) -> bool {
+        self.state == MilestoneState::Accepted ||
+            (!self.deliverables.is_empty() && self.deliverables.iter().all(|deliverable| deliverable.status == DeliverableStatus::Done))
+    }
+
+    /// The due date has passed and the milestone is neither complete nor waiting for the evaluation of its delivery
+    pub fn is_overdue(&self,now:Timestamp) -> bool {
+        self.due != 0 && now > self.due && !self.is_complete() && self.state != MilestoneState::Submitted
     }
 
 }
 
 
 
-pub const MAX_MEM:u32 = 41_943_040; // 5Mbs
+/// Default storage quota per project in bytes (5 MiB), programs can set their own quota
+pub const MAX_MEM:u32 = 5_242_880;
 
 
 
@@ -354,7 +1136,7 @@ pub const MAX_MEM:u32 = 41_943_040; // 5Mbs
 pub struct FetchedMilestone{
     pub id:u8,
     pub edited_per_mile: Option<Vec<EditedMile>>,
-    pub all_edits: Option<Vec<(u8,Vec<EditedMile>)>>,
+    pub all_edits: Option<BTreeMap<u8,Vec<EditedMile>>>,
     pub main: Option<Vec<AddMilestone>>,
     pub pivoted: Option<Vec<AddMilestone>>,
 }
@@ -370,18 +1152,75 @@ pub struct InnerProject {
     pub referenda_no: Option<u32>
 }
 
-#[derive(Encode,Clone,Default, Decode,Debug)]
+/// Milestone layout of the contract before delivery tracking, kept to decode the legacy proposals
+#[derive(Encode,Clone,Default, Decode, Debug)]
 #[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
-pub struct Project{
-    id: u8,
-    pub data: InnerProject,
-    pub edited: Vec<(u8,Vec<EditedMile>)>, // (index == main milestone, value == Vec<EditedMilestonesPerMilestone>)
-    pub main: Vec<AddMilestone>,
-    pub pivoted: Vec<Vec<AddMilestone>>,
-    //Utils
-    pub pivot_reason: Option<Vec<String>>,
-    pub pivot_index: Option<Vec<u8>>,
-    pub total_mem: u32, // total memory used
+pub struct LegacyMilestone {
+    main_index:u8,
+    no_edits:u8,
+    data: String,
+    mem: u32
+}
+
+impl From<LegacyMilestone> for AddMilestone {
+    fn from(legacy: LegacyMilestone) -> Self {
+        AddMilestone::new(legacy.main_index,legacy.no_edits,legacy.data,legacy.mem,MilestoneTerms::default())
+    }
+}
+
+/// Project layout of the contract before grant programs and applications, kept to decode `proposal`
+#[derive(Encode,Clone,Default, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
+pub struct LegacyProject{
+    id: u8,
+    data: InnerProject,
+    edited: Vec<(u8,Vec<EditedMile>)>,
+    main: Vec<LegacyMilestone>,
+    pivoted: Vec<Vec<LegacyMilestone>>,
+    pivot_reason: Option<Vec<String>>,
+    pivot_index: Option<Vec<u8>>,
+    total_mem: u32
+}
+
+impl From<LegacyProject> for Project {
+    fn from(legacy: LegacyProject) -> Self {
+        Self{
+            id: legacy.id,
+            data: legacy.data,
+            edited: legacy.edited.into_iter().collect(),
+            main: legacy.main.into_iter().map(AddMilestone::from).collect(),
+            pivoted: legacy.pivoted.into_iter()
+                .map(|path| path.into_iter().map(AddMilestone::from).collect())
+                .collect(),
+            pivot_reason: legacy.pivot_reason,
+            pivot_index: legacy.pivot_index,
+            total_mem: legacy.total_mem,
+            program: None,
+            ask: 0,
+            status: Status::Draft,
+            application: None,
+        }
+    }
+}
+
+#[derive(Encode,Clone,Default, Decode,Debug)]
+#[cfg_attr(feature = "std", derive(StorageLayout,scale_info::TypeInfo))]
+pub struct Project{
+    id: u8,
+    pub data: InnerProject,
+    pub edited: BTreeMap<u8,Vec<EditedMile>>, // (key == main milestone, value == edit history of the milestone)
+    pub main: Vec<AddMilestone>,
+    pub pivoted: Vec<Vec<AddMilestone>>,
+    //Utils
+    pub pivot_reason: Option<Vec<String>>,
+    pub pivot_index: Option<Vec<u8>>,
+    pub total_mem: u32, // total memory used by the current content in bytes
+    // Grant program the proposal is attached to and the amount asked
+    pub program: Option<u32>,
+    pub ask: Balance,
+    pub status: Status,
+    // Active application `(issuer_id, application_id)` of the proposal
+    pub application: Option<(u16,u32)>,
 }
 
 // Implement Debug Trait manually for Project
@@ -397,56 +1236,210 @@ impl Project {
         Self{
             id: id.saturating_add(1),
             data,
-            edited: vec![],
+            edited: BTreeMap::new(),
             main: vec![],
             pivoted: vec![],
             pivot_reason: None,
             pivot_index: None,
             total_mem: mem,
+            program: None,
+            ask: 0,
+            status: Status::Draft,
+            application: None,
+        }
+    }
+
+    /// Moves the project to the new status if the `actor` is allowed to, returns the previous status
+    pub fn transition(&mut self,status:Status,actor:&Actor) -> ApplicationResult<Status>{
+        if !self.status.can_transition(&status,actor) {
+            return Err(Error::InvalidTransition)
+        }
+        Ok(core::mem::replace(&mut self.status,status))
+    }
+
+    /// Erase the content of the project while keeping its id and chain reference
+    pub fn tombstone(&mut self){
+        self.data.file = String::new();
+        self.edited = BTreeMap::new();
+        self.main = vec![];
+        self.pivoted = vec![];
+        self.pivot_reason = None;
+        self.pivot_index = None;
+        self.total_mem = 0;
+    }
+
+    /// Current milestone path, each pivot branch replaces the tail of the path from its pivot index
+    pub fn active_path(&self) -> Vec<AddMilestone> {
+        let mut path = self.main.clone();
+        let pivot_index = self.pivot_index.clone().unwrap_or_default();
+
+        self.pivoted.iter().zip(pivot_index.iter()).for_each(|(branch,index)|{
+            path.truncate(*index as usize - 1);
+            path.extend(branch.iter().cloned());
+        });
+        path
+    }
+
+    /// Milestone at `mile_no` of the active path, from the latest pivot branch covering it or else the main milestones
+    pub fn milestone_mut(&mut self,mile_no:u8) -> Option<&mut AddMilestone> {
+        let pivot_index = self.pivot_index.clone().unwrap_or_default();
+
+        match pivot_index.iter().enumerate().rev().find(|(_,index)| mile_no >= **index) {
+            Some((branch,index)) => self.pivoted.get_mut(branch)?.get_mut((mile_no - index) as usize),
+            None => self.main.get_mut((mile_no as usize).checked_sub(1)?)
         }
     }
 
-    pub fn add_main(&mut self,mile:AddMilestone,mem:u32) -> Result<(),MilestoneError>{
+    /// Whether the main milestone at `mile_no` was replaced by a pivot branch
+    pub fn is_replaced(&self,mile_no:u8) -> bool {
+        let first_pivot = self.pivot_index.as_ref().and_then(|index| index.iter().min().copied());
+        first_pivot.map_or(false,|index| mile_no >= index)
+    }
+
+    /// Milestones of the active path which are overdue at `now`
+    pub fn overdue(&self,now:Timestamp) -> Vec<AddMilestone> {
+        self.active_path().into_iter().filter(|mile| mile.is_overdue(now)).collect()
+    }
+
+    /// Charges `mem` bytes against the `quota` after refunding the `refund` bytes of replaced content
+    fn charge(&mut self,mem:u32,refund:u32,quota:u32) -> Result<(),MilestoneError>{
         // Check if still u have the memory bandwidth
-        let used_mem = self.total_mem.saturating_add(mem);
-        if used_mem < MAX_MEM {
+        let used_mem = self.total_mem.saturating_sub(refund).saturating_add(mem);
+        if used_mem > quota {
             return Err(MilestoneError::StorageExceeded)
         }
+        self.total_mem = used_mem;
+        Ok(())
+    }
+
+    /// Memory of the latest version of the main milestone, its last edit if edited.
+    /// Replaced milestones were refunded by the pivot and hold no memory.
+    pub fn current_mem(&self,mile_no:u8) -> u32 {
+        if self.is_replaced(mile_no) {
+            return 0
+        }
+        self.edited.get(&mile_no)
+            .and_then(|history| history.last())
+            .map(|edit| edit.mem)
+            .or_else(|| self.main.get((mile_no as usize).checked_sub(1)?).map(|mile| mile.mem))
+            .unwrap_or_default()
+    }
+
+    pub fn add_main(&mut self,mile:AddMilestone,mem:u32,quota:u32) -> Result<(),MilestoneError>{
+        self.charge(mem,0,quota)?;
       
-        self.main.push(mile);
-        // Update the sorage
-        self.total_mem = self.total_mem.saturating_add(mem);
+        // New milestones extend the latest pivot branch
+        if let Some(branch) = self.pivoted.last_mut() {
+            branch.push(mile);
+        }else{
+            self.main.push(mile);
+        }
         Ok(())
     }
 
 
-    pub fn add_edit(&mut self, mile_no: u8, mile:EditedMile,mem:u32) -> Result<(),MilestoneError>{
-        // Check if still u have the memory bandwidth
-        let used_mem = self.total_mem.saturating_add(mem);
-        if used_mem < MAX_MEM {
-            return Err(MilestoneError::StorageExceeded)
+    /// Starts a new branch replacing the milestones from `mile_no` of the active path
+    /// The replaced milestones are refunded from the project's memory
+    pub fn pivot(&mut self,mile_no:u8,reason:String,mile:AddMilestone,mem:u32,quota:u32) -> Result<(),MilestoneError>{
+        if reason.is_empty() {
+            return Err(MilestoneError::PivotReasonRequired)
+        }
+        let path = self.active_path();
+        if mile_no == 0 || mile_no as usize > path.len() {
+            return Err(MilestoneError::MilestoneNotFound)
+        }
+        // Delivered milestones can't be replaced
+        if path[mile_no as usize - 1..].iter().any(|replaced| matches!(replaced.state,MilestoneState::Submitted | MilestoneState::Accepted)) {
+            return Err(MilestoneError::InvalidState)
         }
 
-        // Check if the main milestone is there
-        if let Some(_latest_main) = self.main.get_mut(mile_no as usize - 1){
-            // Update the edited milestines list
-            // -- check if there are edits in place associated with the milestone
-            self.edited.iter_mut().for_each(|v|{
-                // There exists edits for the milestone
-                if v.0 == mile_no {
-                    v.1.push(mile.clone());
-                }else{
-                    
-                }
-            });
-            self.edited.push((mile_no,vec![mile]));
+        // Milestones before the first pivot index are main milestones which can be edited
+        let first_pivot = self.pivot_index.as_ref().and_then(|index| index.iter().min().copied());
+        let refund = path[mile_no as usize - 1..].iter().map(|replaced|{
+            if first_pivot.map_or(true,|index| replaced.main_index < index) {
+                self.current_mem(replaced.main_index)
+            }else{
+                replaced.mem
+            }
+        }).fold(0u32,|total,mem| total.saturating_add(mem));
+        self.charge(mem,refund,quota)?;
+
+        self.pivoted.push(vec![mile]);
+        self.pivot_reason.get_or_insert_with(Vec::new).push(reason);
+        self.pivot_index.get_or_insert_with(Vec::new).push(mile_no);
+        Ok(())
+    }
 
-            Ok(())?
+    /// Milestones of the project, a specific milestone with its edits or the whole tree.
+    /// `pivoted` holds the milestones of the active path coming from pivot branches.
+    pub fn fetch(&self,mile_no:Option<u8>) -> Result<FetchedMilestone,MilestoneError>{
+        let mut result_milestone = FetchedMilestone{
+            id: self.id,
+            ..Default::default()
+        };
+
+        // Check if the milestone is there
+        if self.main.is_empty() {
+            return Err(MilestoneError::MilestoneNotFound)
+        }
+        let path = self.active_path();
+        // Milestones from the first pivot index on come from pivot branches
+        let first_pivot = self.pivot_index.as_ref().and_then(|index| index.iter().min().copied());
+
+        // Check if specific milestone is given
+        if let Some(m_no) = mile_no{
+            let mile = (m_no as usize).checked_sub(1)
+                .and_then(|index| path.get(index))
+                .ok_or(MilestoneError::MilestoneNotFound)?;
+
+            // The main milestone, also when it was replaced by a pivot
+            if let Some(main) = self.main.get(m_no as usize - 1){
+                result_milestone.main = Some(vec![main.clone()]);
+            }
+            if first_pivot.map_or(false,|index| m_no >= index){
+                result_milestone.pivoted = Some(vec![mile.clone()]);
+            }
+
+            // Fetch the edits associated with the milestone
+            result_milestone.edited_per_mile = self.edited.get(&m_no).cloned();
 
         }else{
-            Err(MilestoneError::MilestoneNotFound)?
+            // Construct a fetchedMilestone object to fetch whole tree of milestone nodes
+            result_milestone.main = Some(self.main.clone());
+            // Fetch all the edits per milestons, if there are no edits leave the ResultMilestone as it is
+            if !self.edited.is_empty(){
+                result_milestone.all_edits = Some(self.edited.clone());
+            }
+
+            if !self.pivoted.is_empty(){
+                result_milestone.pivoted = Some(path);
+            }
         }
-       
+
+        Ok(result_milestone)
+    }
+
+
+    /// The edit replaces the latest version of the milestone, which is refunded
+    pub fn add_edit(&mut self, mile_no: u8, mile:EditedMile,mem:u32,quota:u32) -> Result<(),MilestoneError>{
+
+        // Check if the main milestone is there
+        let index = (mile_no as usize).checked_sub(1).ok_or(MilestoneError::MilestoneNotFound)?;
+        if index >= self.main.len() {
+            return Err(MilestoneError::MilestoneNotFound)
+        }
+        if self.is_replaced(mile_no) {
+            return Err(MilestoneError::MilestoneReplaced)
+        }
+        let edits = self.edited.get(&mile_no).map_or(0,|history| history.len());
+        let no_edits = u8::try_from(edits + 1).map_err(|_| MilestoneError::MaxEditsExceeded)?;
+        let refund = self.current_mem(mile_no);
+        self.charge(mem,refund,quota)?;
+
+        // Update the edit history of the milestone
+        self.edited.entry(mile_no).or_default().push(mile);
+        self.main[index].no_edits = no_edits;
+
         Ok(())
     }
 
@@ -483,6 +1476,7 @@ pub trait CreateProfile {
     ) -> CreateResult<()>;
 
 
+    /// Updates the caller's Individual Profile, only the provided fields are changed
     #[ink(message, selector = 0xC0DE0002)]
     fn update_individual_profile(
         &mut self,
@@ -497,6 +1491,8 @@ pub trait CreateProfile {
     ///  `optional applicant profile`, `team-size`, `description`
     /// The optional account act as Team's profile account. If not provided caller's
     /// account will be used as Team's profile account.
+    /// The `members` are invited to the team and only join once they accept the invitation.
+    /// The applicant and the invited admins must fit in [DEFAULT_MAX_KEYS].
     ///
     /// In Phala context this function will be dispatched following block production
     /// as it takes in `&mut self`.
@@ -515,52 +1511,343 @@ pub trait CreateProfile {
     ) -> CreateResult<()>;
 
 
-    // / Creates Grant Issuer Profile, a function which takes on `name: String`,
-    // / `grant_levels`: This is an optional parameter whereby Issuer can choose different levels
-    // / of grants to provide based on amount, `chain type if the grants is an on-chain type and
-    // / None if its Off-chain`, `categories`: This specifies which categories this grant is on.
-    // / `description`: extra details of the grants.
-    // /
-    // / Allowed Accounts act as privileged members that can control the account `Multi-Key system`
-    // / In Phala context this function will be dispatched following block production
-    // / as it takes in `&mut self`.
-    //--------------------------------------------------------------------------------------------
-    
-   
+    /// Updates Applicant Profile, only the provided fields are changed.
+    /// Members with the `EditProfile` capability can update the profile, `team` selects
+    /// which team when the caller belongs to several teams.
+    #[ink(message,selector =0xC0DE0005)]
+    fn update_applicant_profile(
+        &mut self,
+        team: Option<AccountId>,
+        name: Option<String>,
+        description: Option<String>,
+        mission: Option<String>,
+        categories: Option<Vec<Categories>>,
+        chain: Option<Vec<Chains>>,
+        links: Option<Vec<String>>,
+    ) -> CreateResult<()>;
+
+
+    /// Creates Grant Issuer Profile, a function which takes on `name: String`,
+    /// `grant_levels`: This is an optional parameter whereby Issuer can choose different levels
+    /// of grants to provide based on amount, `chain type if the grants is an on-chain type and
+    /// None if its Off-chain`, `categories`: This specifies which categories this grant is on.
+    /// `description`: extra details of the grants, `requirements`: what applications must include.
+    /// The optional account act as Issuer's profile account. If not provided caller's
+    /// account will be used as Issuer's profile account.
+    ///
+    /// Allowed Accounts act as privileged members that can control the account `Multi-Key system`
+    /// In Phala context this function will be dispatched following block production
+    /// as it takes in `&mut self`.
+    #[ink(message, selector = 0xC0DE0040)]
+    fn create_issuer_profile(
+        &mut self,
+        name: String,
+        account: Option<AccountId>,
+        description: String,
+        chain: Vec<Chains>,
+        categories: Vec<Categories>,
+        grant_levels: Vec<GrantLevel>,
+        requirements: Vec<String>,
+        allowed_keys: Vec<AccountId>,
+    ) -> CreateResult<()>;
+
+
+    /// Adding and removing allowed keys of the issuer, only the issuer's `admin` can update them.
+    /// `ChangeAdmin` hands the issuer over to one of its allowed keys, the admin key can not be removed.
+    #[ink(message, selector = 0xC0DE005A)]
+    fn update_issuer_keys(&mut self,issuer_id: u16,account: AccountId,action: KeyAction) -> CreateResult<()>;
+
 
 
     /// Adding and removing allowed accounts by the `admin`
     /// This will allow not only one person who is privileged to manage an account but also
-    /// multiple allowed accounts. Max allowed accounts is a fixed constant [MAX_KEYS].
+    /// multiple allowed accounts. Max allowed accounts is the team's `max_keys`, [DEFAULT_MAX_KEYS] by default.
     ///
-    /// Worst case scenario, time complexity will be `O(n)` with a best case of `O(1)`
+    /// `team` is the team's `key_pointer`, it is required when the caller is admin of several teams.
+    /// `ChangeAdmin` starts a time locked handover which the new admin has to accept, see [KeyRecovery].
+    ///
+    /// Keys are looked up through the key index, time complexity is `O(1)` regardless of the number of teams
     /// In Phala context this function will be dispatched following block production
     /// as it takes in `&mut self`.
     #[ink(message, selector = 0xC0DE0004)]
-    fn update_keys(&mut self,account: AccountId,action: KeyAction) -> CreateResult<()>;
+    fn update_keys(&mut self,team: Option<AccountId>,account: AccountId,action: KeyAction) -> CreateResult<()>;
+
+
+    /// Deletes the caller's Individual Profile and unlinks it from the referenced teams.
+    /// The proposals are handled following the `policy`, the caller can not be an allowed key of any team
+    /// and the escrows of its projects have to be settled.
+    #[ink(message, selector = 0xC0DE0020)]
+    fn delete_individual_profile(&mut self,policy: ErasurePolicy) -> CreateResult<()>;
+
+
+    /// Deletes the Applicant Profile administered by the caller together with its keys, roles,
+    /// pending operations, invitations and recovery state.
+    /// The proposals are handled following the `policy`, the escrows of its projects have to be settled.
+    #[ink(message, selector = 0xC0DE0021)]
+    fn delete_applicant_profile(&mut self,team: Option<AccountId>,policy: ErasurePolicy) -> CreateResult<()>;
+
+
+
+}
+
+
+/// Trait definition for M-of-N approvals of sensitive team actions (Team as Daos)
+/// Submitting a proposal, pivoting a milestone, changing the admin, removing keys and changing the
+/// threshold are queued as pending operations when the team's threshold is higher than one.
+/// The operation is executed once enough allowed keys approve it, the proposer counting as the first approval.
+#[ink::trait_definition]
+pub trait MultiKey {
+
+    /// Sets the number of allowed keys required to approve sensitive actions.
+    /// At a threshold of 1 only the admin can change it, above it the change is queued for approvals.
+    #[ink(message, selector = 0xC0DE0022)]
+    fn set_threshold(&mut self,team: Option<AccountId>,threshold: u8) -> CreateResult<()>;
+
+    /// Approves a pending operation, executes it once the threshold is reached
+    #[ink(message, selector = 0xC0DE0023)]
+    fn approve_operation(&mut self,team: Option<AccountId>,operation_id: u32) -> CreateResult<()>;
+
+    /// Cancels a pending operation, only the proposer or the admin can cancel
+    #[ink(message, selector = 0xC0DE0024)]
+    fn cancel_operation(&mut self,team: Option<AccountId>,operation_id: u32) -> CreateResult<()>;
+
+    /// Sets the team's limit of allowed keys within the platform ceiling, only the admin can set it
+    #[ink(message, selector = 0xC0DE0039)]
+    fn set_max_keys(&mut self,team: Option<AccountId>,max_keys: u8) -> CreateResult<()>;
+
+}
+
+
+/// Trait definition for team roles and their capabilities
+/// `Admin` has every capability, `Regular` has [REGULAR_CAPABILITIES] and teams can define custom roles.
+#[ink::trait_definition]
+pub trait TeamPermissions {
+
+    /// Defines a custom role for the team, requires `ManageMembers`.
+    /// `ManageKeys` and `ApproveOperations` stay with the keys and can't be part of a role.
+    #[ink(message, selector = 0xC0DE0025)]
+    fn define_role(&mut self,team: Option<AccountId>,name: String,capabilities: Vec<Capability>) -> CreateResult<()>;
 
+    /// Assigns a role to a team member, requires `ManageMembers`.
+    /// `Admin` comes with a key and is granted through `update_keys` or an admin invitation.
+    #[ink(message, selector = 0xC0DE0026)]
+    fn assign_role(&mut self,team: Option<AccountId>,member: AccountId,role: MemberRole) -> CreateResult<()>;
 
+}
+
+
+/// Trait definition for team membership
+/// Members join a team by accepting an invitation, the invitation expires after the
+/// given time or [DEFAULT_INVITATION_TTL].
+/// Leaving or removal clears the team's member list, allowed keys and the individual's referenced team.
+#[ink::trait_definition]
+pub trait Membership {
+
+    /// Invites an account to the team, requires `ManageMembers` and only admins can invite as `Admin`
+    #[ink(message, selector = 0xC0DE0027)]
+    fn invite_member(&mut self,team: Option<AccountId>,account: AccountId,role: MemberRole,expires_in: Option<Timestamp>) -> CreateResult<()>;
+
+    /// Joins the team, invited admins are also granted a key.
+    /// An expired invitation is removed and `InvitationLapsed` is emitted instead of joining.
+    #[ink(message, selector = 0xC0DE0028)]
+    fn accept_invitation(&mut self,team: AccountId) -> CreateResult<()>;
+
+    #[ink(message, selector = 0xC0DE0029)]
+    fn decline_invitation(&mut self,team: AccountId) -> CreateResult<()>;
+
+    /// Removes a member from the team, requires `ManageMembers` and only admins can remove admins.
+    /// Removing a key holder is queued when the team requires several approvals.
+    #[ink(message, selector = 0xC0DE0030)]
+    fn remove_member(&mut self,team: Option<AccountId>,member: AccountId) -> CreateResult<()>;
+
+    #[ink(message, selector = 0xC0DE0031)]
+    fn leave_team(&mut self,team: AccountId) -> CreateResult<()>;
+
+}
+
+
+/// Trait definition for admin handover and key recovery
+/// Changing the admin is done in two phases, the new admin accepts once the handover delay
+/// has passed and any other allowed key can veto during the delay.
+/// When the admin key is lost the guardians can jointly appoint a new admin after the recovery delay.
+#[ink::trait_definition]
+pub trait KeyRecovery {
+
+    /// Sets the handover delay and the guardians, queued when the team requires several approvals.
+    /// Shortening the handover delay, dropping guardians or raising the guardians' threshold or delay
+    /// waits out the current handover delay, see `execute_delayed_operation`.
+    #[ink(message, selector = 0xC0DE0032)]
+    fn set_recovery_config(&mut self,team: Option<AccountId>,config: RecoveryConfig) -> CreateResult<()>;
+
+    /// Called by the new admin to complete the handover
+    #[ink(message, selector = 0xC0DE0033)]
+    fn accept_admin(&mut self,team: AccountId) -> CreateResult<()>;
+
+    /// Cancels a pending handover, any allowed key except the proposer or a key whose removal is delayed can veto
+    #[ink(message, selector = 0xC0DE0034)]
+    fn veto_admin_handover(&mut self,team: Option<AccountId>) -> CreateResult<()>;
+
+    /// Starts the appointment of a new admin by a guardian, only once the previous request lapsed
+    #[ink(message, selector = 0xC0DE0035)]
+    fn initiate_recovery(&mut self,team: AccountId,new_admin: AccountId) -> CreateResult<()>;
+
+    #[ink(message, selector = 0xC0DE0036)]
+    fn support_recovery(&mut self,team: AccountId) -> CreateResult<()>;
+
+    /// Appoints the new admin once enough guardians agree and the recovery delay has passed
+    #[ink(message, selector = 0xC0DE0037)]
+    fn execute_recovery(&mut self,team: AccountId) -> CreateResult<()>;
+
+    /// Withdraws the calling guardian's support, the request is cancelled once no guardian supports it.
+    /// The admin being recovered against can't cancel.
+    #[ink(message, selector = 0xC0DE0038)]
+    fn cancel_recovery(&mut self,team: AccountId) -> CreateResult<()>;
+
+    /// Executes a time locked operation once the handover delay has passed, requires an allowed key
+    #[ink(message, selector = 0xC0DE0058)]
+    fn execute_delayed_operation(&mut self,team: AccountId,operation_id: u32) -> CreateResult<()>;
+
+    /// Vetoes a time locked operation during the delay, any allowed key except the proposer or any guardian can veto.
+    /// The account being removed can't veto its own removal.
+    #[ink(message, selector = 0xC0DE0059)]
+    fn veto_delayed_operation(&mut self,team: AccountId,operation_id: u32) -> CreateResult<()>;
+
+}
+
+
+/// Trait definition for grant programs published by issuers
+/// Only the allowed keys of the issuer can create, update and close its programs.
+/// Applicants attach their proposals to open programs, teams need `SubmitProposal` to do so.
+#[ink::trait_definition]
+pub trait GrantPrograms {
+
+    /// Publishes a new program for the issuer, `open` and `close` are block timestamps
+    #[ink(message, selector = 0xC0DE0041)]
+    fn create_program(
+        &mut self,
+        issuer_id: u16,
+        name: String,
+        open: Timestamp,
+        close: Timestamp,
+        budget: Balance,
+        categories: Vec<Categories>,
+        chains: Vec<Chains>,
+        max_ask: Balance,
+        template: String,
+    ) -> ApplicationResult<()>;
+
+    /// Updates the program details, closed programs can not be updated
+    #[ink(message, selector = 0xC0DE0042)]
+    fn update_program(
+        &mut self,
+        program_id: u32,
+        name: String,
+        open: Timestamp,
+        close: Timestamp,
+        budget: Balance,
+        categories: Vec<Categories>,
+        chains: Vec<Chains>,
+        max_ask: Balance,
+        template: String,
+    ) -> ApplicationResult<()>;
+
+    /// Closes the program before its close time
+    #[ink(message, selector = 0xC0DE0043)]
+    fn close_program(&mut self,program_id: u32) -> ApplicationResult<()>;
+
+    /// Sets the storage quota in bytes of the projects attached to the program,
+    /// it can not be lower than the storage already used by one of them
+    #[ink(message, selector = 0xC0DE0051)]
+    fn set_program_quota(&mut self,program_id: u32,quota: u32) -> ApplicationResult<()>;
+
+    /// Attaches the proposal to an open program.
+    /// The proposal's chain and one of the applicant's categories have to be eligible,
+    /// the `ask` can not exceed the program's `max_ask` nor its `budget`
+    #[ink(message, selector = 0xC0DE0044)]
+    fn attach_proposal(&mut self,team: Option<AccountId>,proposal_id: u8,program_id: u32,ask: Balance) -> ApplicationResult<()>;
 
 }
 
 
 /// Trait defination for grant application process (Offchain e.g web3 foundation grant type)
 /// This can be used by both Applicants and Issuers
+/// Applications are queued per issuer and identified by `(issuer_id, application_id)`
 #[ink::trait_definition]
 pub trait OffchainApply {
 
     /// Naming convention for Offchain grant type
     /// This function creates new application, stores is externally and commits the reference to the chain
+    /// `file` is the reference of the application file, the proposal has to belong to the applicant
+    /// and if attached to a program the program has to be the issuer's.
+    /// Team members need `SubmitProposal` to apply.
     #[ink(message,selector = 0xC0DE0006)]
-    fn apply_grant(&mut self) -> ApplicationResult<()>;
+    fn apply_grant(&mut self,team: Option<AccountId>,issuer_id: u16,proposal_id: u8,file: String) -> ApplicationResult<()>;
 
+    /// Replaces the application file, an application with requested changes is submitted again
     #[ink(message,selector = 0xC0DE0007)]
-    fn update_application(&mut self) -> ApplicationResult<()>;
+    fn update_application(&mut self,issuer_id: u16,application_id: u32,file: String) -> ApplicationResult<()>;
 
+    /// Moves the application and its proposal along their lifecycle, see [Status].
+    /// Only the issuer's allowed keys can review, approve and mark the proposal as funded or completed.
+    /// Approving, rejecting or requesting changes publishes the decision of the review round, see [GrantReview].
     #[ink(message,selector = 0xC0DE0008)]
-    fn review_application(&mut self) -> ApplicationResult<()>;
+    fn review_application(&mut self,issuer_id: u16,application_id: u32,status: Status,feedback: Option<String>) -> ApplicationResult<()>;
+
+    /// Abandons the proposal together with its active application
+    #[ink(message,selector = 0xC0DE0045)]
+    fn abandon_proposal(&mut self,team: Option<AccountId>,proposal_id: u8) -> ApplicationResult<()>;
+}
+
+/// Trait definition for reviewing applications with a weighted rubric
+/// The issuer assigns reviewers who score each criterion of the rubric, the decision is published
+/// through `review_application` together with the aggregate score.
+/// Reviews stay hidden from the applicant until the decision is published.
+#[ink::trait_definition]
+pub trait GrantReview {
+
+    /// Sets the rubric used for the next reviewer assignments of the issuer
+    #[ink(message,selector = 0xC0DE0046)]
+    fn set_rubric(&mut self,issuer_id: u16,criteria: Vec<Criterion>) -> ApplicationResult<()>;
+
+    /// Assigns the reviewers of a submitted application and moves it under review.
+    /// Assigning again before the decision replaces the reviewers and keeps their reviews.
+    #[ink(message,selector = 0xC0DE0047)]
+    fn assign_reviewers(&mut self,issuer_id: u16,application_id: u32,reviewers: Vec<AccountId>) -> ApplicationResult<()>;
+
+    /// Submits or replaces the caller's review, `comments` is the reference of the comments file
+    #[ink(message,selector = 0xC0DE0048)]
+    fn submit_review(&mut self,issuer_id: u16,application_id: u32,scores: Vec<u8>,comments: String) -> ApplicationResult<()>;
+
+    /// Declares a conflict of interest on the application, visible to the issuer.
+    /// Only the assigned reviewers can declare, declaring again replaces the reason.
+    /// Reviewers on the applicant team are blocked without declaring.
+    #[ink(message,selector = 0xC0DE0049)]
+    fn declare_conflict(&mut self,issuer_id: u16,application_id: u32,reason: String) -> ApplicationResult<()>;
+
+    /// Steps down from reviewing the application, the caller's review is discarded
+    /// and it can not be assigned again
+    #[ink(message,selector = 0xC0DE0050)]
+    fn recuse(&mut self,issuer_id: u16,application_id: u32,reason: String) -> ApplicationResult<()>;
+}
+
+/// Trait definition for escrowed milestone payouts in the native balance
+/// The escrow is bound to the issuer the project applied to when the first deposit is made.
+/// Accepting a milestone pays its `amount` to the project owner, the team's `key_pointer` or the individual.
+#[ink::trait_definition]
+pub trait GrantEscrow {
+
+    /// Deposits the transferred value into the escrow of the `owner`'s project, an approved project becomes funded
+    #[ink(message,payable,selector = 0xC0DE0056)]
+    fn deposit_escrow(&mut self,owner: AccountId,proposal_id: u8) -> ApplicationResult<()>;
+
+    /// Transfers the unspent escrow to the caller once the proposal is abandoned by the applicant,
+    /// rejected by the issuer or completed, closing the program does not cancel its grants
+    #[ink(message,selector = 0xC0DE0057)]
+    fn reclaim_escrow(&mut self,owner: AccountId,proposal_id: u8) -> ApplicationResult<()>;
+
 }
 
+
 /// Trait definition for on-chain grant application process (e.g Kusama treasury )
 #[ink::trait_definition]
 pub trait OnchainGrant {
@@ -589,40 +1876,67 @@ pub trait OnchainGrant {
 // Pivoted --------------------------------------------------> P_M7 --> P_M8 --> P_M9
 
 /// Trait fot Milestone tracking functionalities
+/// `team` selects the team's projects, if not provided the caller's first team is used
+/// or the individual profile when the caller is not in any team.
+/// Team members need `EditMilestone` to change milestones and `ViewPrivateData` to fetch them.
 #[ink::trait_definition]
 pub trait MilestoneTracker {
     
+    /// Adds a milestone with its due date, requested amount and deliverables
     #[ink(message, selector = 0xC0DE0010)]
-    fn add_milestone(&mut self,project:u8,file:String,mem:u32) -> MilestoneResult<()>;
+    fn add_milestone(&mut self,team:Option<AccountId>,project:u8,file:String,mem:u32,terms:MilestoneTerms) -> MilestoneResult<()>;
 
+    /// Edits a main milestone, milestones replaced by a pivot can only be changed by pivoting again
     #[ink(message, selector = 0xC0DE0011)]
-    fn edit_milestone(&mut self,project:u8,mile_no:u8,file:String,mem:u32) -> MilestoneResult<()>;
+    fn edit_milestone(&mut self,team:Option<AccountId>,project:u8,mile_no:u8,file:String,mem:u32) -> MilestoneResult<()>;
 
+    /// Replaces the milestones from `mile_no` with a new branch starting with `file`.
+    /// The `reason` is required and kept with the pivot index, following milestones extend the branch.
     #[ink(message, selector = 0xC0DE0012)]
-    fn pivote_milestone(&mut self,project:u8,mile_no:u8,file:String,mem:u32) -> MilestoneResult<()>;
+    fn pivote_milestone(&mut self,team:Option<AccountId>,project:u8,mile_no:u8,reason:String,file:String,mem:u32,terms:MilestoneTerms) -> MilestoneResult<()>;
+
+    /// Updates the status of a deliverable of the milestone at `mile_no` of the current path.
+    /// Deliverables of submitted and accepted milestones can not be changed.
+    #[ink(message, selector = 0xC0DE0052)]
+    fn update_deliverable(&mut self,team:Option<AccountId>,project:u8,mile_no:u8,deliverable:u8,status:DeliverableStatus) -> MilestoneResult<()>;
+
+    /// Submits the milestone at `mile_no` of the current path as delivered, `evidence` is the reference of the evidence file.
+    /// The proposal has to be approved or funded.
+    #[ink(message, selector = 0xC0DE0053)]
+    fn submit_milestone_delivery(&mut self,team:Option<AccountId>,project:u8,mile_no:u8,evidence:String) -> MilestoneResult<()>;
+
+    /// Accepts, rejects or requests changes on a submitted milestone of the `owner`'s project.
+    /// Only the allowed keys of the issuer the project applied to can accept or reject, the application's
+    /// reviewers can request changes unless they have a conflict of interest on the application.
+    // Not 0xC0DE0054, the trait id is derived from the selectors and would collide with `GrantPrograms`
+    #[ink(message, selector = 0xC0DE0055)]
+    fn evaluate_milestone(&mut self,owner:AccountId,project:u8,mile_no:u8,state:MilestoneState,comments:Option<String>) -> MilestoneResult<()>;
 
     /// Flexible to fetch any stage of the milestone
     /// Annotate which depth of the edits you want to receive, default set to all edits
     #[ink(message, selector = 0xC0DE0013)]
-    fn fetch_milestone(&self,project_id:u8,mile_no:Option<u8>) -> MilestoneResult<FetchedMilestone>;
+    fn fetch_milestone(&self,team:Option<AccountId>,project_id:u8,mile_no:Option<u8>) -> MilestoneResult<FetchedMilestone>;
     
 }
 
 
 /// Trait fot Proposal application
+/// `team` selects the team's proposals, if not provided the caller's first team is used
+/// or the individual profile when the caller is not in any team.
+/// Team members need `SubmitProposal` to add proposals and `ViewPrivateData` to fetch them.
 #[ink::trait_definition]
 pub trait Proposer {
     
     #[ink(message, selector = 0xC0DE0014)]
-    fn add_proposal(&mut self,chain:Chains,ref_no:Option<u32>,file:String,mem:u32) -> MilestoneResult<()>;
+    fn add_proposal(&mut self,team:Option<AccountId>,chain:Chains,ref_no:Option<u32>,file:String,mem:u32) -> MilestoneResult<()>;
 
 
     #[ink(message, selector = 0xC0DE0015)]
-    fn fetch_proposal(&self,proposal_id:u8) -> MilestoneResult<Project>;
+    fn fetch_proposal(&self,team:Option<AccountId>,proposal_id:u8) -> MilestoneResult<Project>;
 
 
     #[ink(message, selector = 0xC0DE0019)]
-    fn fetch_latest_proposal_id(&self) -> MilestoneResult<u8>;
+    fn fetch_latest_proposal_id(&self,team:Option<AccountId>) -> MilestoneResult<u8>;
 }
 
 
@@ -647,7 +1961,7 @@ pub trait OffchainDbAuth {
 #[ink::contract]
 mod ordum {
 
-    use ink::storage::Mapping;
+    use ink::storage::{Mapping,Lazy};
     use pink_extension as pink;
     use ink_env::hash::{CryptoHash,Blake2x128};
     use scale::Encode;
@@ -655,8 +1969,17 @@ mod ordum {
 
     use crate::{Categories,AddMilestone,EditedMile,
         Chains, CreateResult, 
-        KeyAction, KeyManagement, MemberRole,UserRole, 
-        Project, FetchedMilestone,Proposer,OffchainDbAuth
+        KeyAction, KeyManagement, LegacyKeyManagement, LegacyProject, MemberRole,UserRole,ProfileField,ErasurePolicy,
+        TeamOperation,PendingOperation,MultiKey,
+        Capability,TeamRole,TeamPermissions,REGULAR_CAPABILITIES,
+        DEFAULT_MAX_KEYS,DEFAULT_KEY_CEILING,UPGRADE_OWNER,
+        Invitation,Membership,DEFAULT_INVITATION_TTL,
+        RecoveryConfig,AdminHandover,RecoveryRequest,DelayedOperation,KeyRecovery,RECOVERY_REQUEST_TTL,
+        GrantLevel,IssuerProfile,MAX_MEM,GrantProgram,GrantPrograms,ApplicationResult,
+        Application,Status,Actor,OffchainApply,
+        Criterion,Review,Decision,ReviewRound,GrantReview,MAX_SCORE,MAX_REASON_LEN,MAX_CONFLICTS,Conflict,
+        Project, FetchedMilestone,Proposer,OffchainDbAuth,
+        MilestoneTerms,DeliverableStatus,MilestoneState,Escrow,GrantEscrow
     };
     use super::{Vec,vec,CreateProfile,String,
         TeamApplicantProfile,
@@ -666,21 +1989,74 @@ mod ordum {
 
 
     /// Ordum Global State
+    ///
+    /// Fields added after the first deployment are `Lazy` or `Mapping` so they live outside the
+    /// root storage cell, and the root written by the previous code still decodes after `set_code`.
     #[ink(storage)]
     pub struct OrdumState {
+        // Contract owner, sets the platform wide settings
+        owner: Lazy<AccountId>,
+        // Platform wide ceiling of allowed keys per team, `DEFAULT_KEY_CEILING` when not set
+        key_ceiling: Lazy<u8>,
+        // Storage quota in bytes of projects outside programs with their own quota, `MAX_MEM` when not set
+        default_quota: Lazy<u32>,
         individual_profile: Mapping<AccountId,IndividualProfile>,
         all_individuals: Vec<(String,AccountId)>,
 
         team_applicant_profile: Mapping<AccountId,TeamApplicantProfile>,
         all_applicant_teams: Vec<(String,AccountId)>,
 
-        manage_keys: Vec<KeyManagement>,
-        proposal: Mapping<AccountId,Vec<Project>>,
-        db_auth: Mapping<AccountId,(String,Vec<u8>)>
-        // Mapping issuer_id to a mapping of  application number to application profile
+        // Legacy key storage, drained into `key_wallets` and `key_index` by `migrate_keys`
+        manage_keys: Vec<LegacyKeyManagement>,
+        // Mapping of allowed key to the key pointers of the teams it belongs to
+        key_index: Mapping<AccountId,Vec<AccountId>>,
+        // Mapping of key pointer to the team's key management
+        key_wallets: Mapping<AccountId,KeyManagement>,
+        // Mapping of key pointer to the team's operations waiting for approvals
+        pending_operations: Mapping<AccountId,Vec<PendingOperation>>,
+        operation_nonce: Mapping<AccountId,u32>,
+        // Mapping of key pointer to the custom roles defined by the team
+        team_roles: Mapping<AccountId,Vec<TeamRole>>,
+        // Mapping of invited account to its pending team invitations and of key pointer to the invited accounts
+        invitations: Mapping<AccountId,Vec<Invitation>>,
+        invited: Mapping<AccountId,Vec<AccountId>>,
+        // Mapping of key pointer to the team's admin handover and recovery state
+        recovery_configs: Mapping<AccountId,RecoveryConfig>,
+        admin_handovers: Mapping<AccountId,AdminHandover>,
+        recovery_requests: Mapping<AccountId,RecoveryRequest>,
+        // Mapping of key pointer to the approved operations waiting out the handover delay
+        delayed_operations: Mapping<AccountId,Vec<DelayedOperation>>,
+
+        // Mapping of issuer id to the issuer profile and of the issuer key pointer to its id
+        issuer_profile: Mapping<u16,IssuerProfile>,
+        issuer_ids: Mapping<AccountId,u16>,
+        all_issuers: Lazy<Vec<(String,u16)>>,
+        // Mapping of program id to the grant program and of issuer id to its programs
+        grant_programs: Mapping<u32,GrantProgram>,
+        issuer_programs: Mapping<u16,Vec<u32>>,
+        program_count: Lazy<u32>,
+
+        // Legacy proposal storage, moved into `projects` by `migrate_proposals`
+        proposal: Mapping<AccountId,Vec<LegacyProject>>,
+        db_auth: Mapping<AccountId,(String,Vec<u8>)>,
+        // Mapping of proposal owner to its projects
+        projects: Mapping<AccountId,Vec<Project>>,
+        // Proposals of deleted profiles, kept following the `ErasurePolicy`
+        archived_proposals: Mapping<AccountId,Vec<Project>>,
+        // Mapping issuer_id and application number to application profile
         // As this will enable specifi grant issuer to have dedicated list of queue application
         // and also teams to have numerous application per one issuer
-        //queue_applications:Mapping<u16,Mapping<u32,u32>>,
+        queue_applications: Mapping<(u16,u32),Application>,
+        application_count: Mapping<u16,u32>,
+        // Mapping of applicant to its applications `(issuer_id, application_id)`
+        applicant_applications: Mapping<AccountId,Vec<(u16,u32)>>,
+        // Mapping of issuer id to its review rubric and of application to its review round
+        rubrics: Mapping<u16,Vec<Criterion>>,
+        reviews: Mapping<(u16,u32),ReviewRound>,
+        // Mapping of application to the conflicts declared by reviewers
+        conflicts: Mapping<(u16,u32),Vec<Conflict>>,
+        // Mapping of (proposal owner, proposal id) to the escrow paying its milestones
+        escrows: Mapping<(AccountId,u8),Escrow>,
         
 
     }
@@ -696,6 +2072,26 @@ mod ordum {
         }
 
 
+    /// Event emitted when individual updates the profile
+        #[ink(event)]
+        pub struct IndividualProfileUpdated {
+            #[ink(topic)]
+            account: AccountId,
+            fields: Vec<ProfileField>,
+            time: Timestamp
+        }
+
+
+    /// Event emitted when individual deletes the profile
+        #[ink(event)]
+        pub struct IndividualProfileDeleted {
+            #[ink(topic)]
+            account: AccountId,
+            policy: ErasurePolicy,
+            time: Timestamp
+        }
+
+
     /// Event emitted when new Applicant is registered
         #[ink(event)]
         pub struct TeamApplicantCreated {
@@ -709,69 +2105,623 @@ mod ordum {
         pub struct TeamApplicantUpdated {
             #[ink(topic)]
             name: String,
+            fields: Vec<ProfileField>,
             time: Timestamp
         }
     
-    /// Event for setting passcode
+    /// Event emitted when Applicant deletes the profile
         #[ink(event)]
-        pub struct PasscodeSet {
+        pub struct TeamApplicantDeleted {
             #[ink(topic)]
-            account: AccountId
+            name: String,
+            account: AccountId,
+            policy: ErasurePolicy,
+            time: Timestamp
+        }
+    
+    /// Event emitted when the admin updates the team's keys
+        #[ink(event)]
+        pub struct KeysUpdated {
+            #[ink(topic)]
+            team: AccountId,
+            action: KeyAction,
+            account: AccountId,
+            time: Timestamp
         }
 
-    /// Event for notifying a reference team link has been updated per individual profile
+    /// Event emitted when a sensitive team action is queued for approvals
         #[ink(event)]
-        pub struct UpdatedTeamMembership {
+        pub struct OperationProposed {
             #[ink(topic)]
-            team_name: String,
-            team_id: AccountId,
-            individual_id: AccountId,
+            team: AccountId,
+            id: u32,
+            proposer: AccountId,
+            operation: TeamOperation,
             time: Timestamp
         }
 
+    /// Event emitted when an allowed key approves a pending operation
+        #[ink(event)]
+        pub struct OperationApproved {
+            #[ink(topic)]
+            team: AccountId,
+            id: u32,
+            approver: AccountId,
+            approvals: u8,
+            threshold: u8
+        }
 
-    impl OrdumState {
+    /// Event emitted when a pending operation reaches the threshold and is executed
+        #[ink(event)]
+        pub struct OperationExecuted {
+            #[ink(topic)]
+            team: AccountId,
+            id: u32,
+            time: Timestamp
+        }
 
-        #[ink(constructor)]
-        pub fn new() -> Self{
-                              
-                Self {
-                    individual_profile: Mapping::default(),
-                    all_individuals: vec![],
-                    team_applicant_profile: Mapping::default(),
-                    all_applicant_teams: vec![],
-                    manage_keys: vec![],
-                    proposal: Mapping::default(),
-                    db_auth: Mapping::default()
-                }
+    /// Event emitted when a pending operation is cancelled
+        #[ink(event)]
+        pub struct OperationCancelled {
+            #[ink(topic)]
+            team: AccountId,
+            id: u32,
+            by: AccountId
         }
 
-        // Remember Upgradability
-        /// Modifies the code which is used to execute calls to this contract address (`AccountId`).
-        ///
-        /// We use this to upgrade the contract logic. We don't do any authorization here, any caller
-        /// can execute this method. In a production contract you would do some authorization here.
-        #[ink(message, selector = 0xC0DE2000 )]
-        pub fn set_code(&mut self, code_hash: [u8; 32]) {
-            ink::env::set_code_hash(&code_hash).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to `set_code_hash` to {:?} due to {:?}",
-                    code_hash, err
-                )
-            });
-            ink::env::debug_println!(" Switched code hash to {:?}.", code_hash);
+    /// Event emitted when an approved operation has to wait out the handover delay
+        #[ink(event)]
+        pub struct OperationDelayed {
+            #[ink(topic)]
+            team: AccountId,
+            id: u32,
+            operation: TeamOperation,
+            executable_at: Timestamp
         }
 
-       
+    /// Event emitted when the team's approval threshold is changed
+        #[ink(event)]
+        pub struct ThresholdUpdated {
+            #[ink(topic)]
+            team: AccountId,
+            threshold: u8
+        }
+
+    /// Event emitted when a team defines a custom role
+        #[ink(event)]
+        pub struct RoleDefined {
+            #[ink(topic)]
+            team: AccountId,
+            id: u8,
+            name: String
+        }
+
+    /// Event emitted when a team member gets a new role
+        #[ink(event)]
+        pub struct RoleAssigned {
+            #[ink(topic)]
+            team: AccountId,
+            member: AccountId,
+            role: MemberRole
+        }
+
+    /// Event emitted when the team's handover delay or guardians are changed
+        #[ink(event)]
+        pub struct RecoveryConfigUpdated {
+            #[ink(topic)]
+            team: AccountId,
+            config: RecoveryConfig
+        }
+
+    /// Event emitted when a new admin is proposed
+        #[ink(event)]
+        pub struct AdminHandoverProposed {
+            #[ink(topic)]
+            team: AccountId,
+            new_admin: AccountId,
+            executable_at: Timestamp
+        }
+
+    /// Event emitted when an allowed key vetoes an admin handover
+        #[ink(event)]
+        pub struct AdminHandoverVetoed {
+            #[ink(topic)]
+            team: AccountId,
+            by: AccountId
+        }
+
+    /// Event emitted when the guardians start appointing a new admin
+        #[ink(event)]
+        pub struct RecoveryInitiated {
+            #[ink(topic)]
+            team: AccountId,
+            new_admin: AccountId,
+            guardian: AccountId,
+            executable_at: Timestamp
+        }
+
+    /// Event emitted when a guardian supports the recovery
+        #[ink(event)]
+        pub struct RecoverySupported {
+            #[ink(topic)]
+            team: AccountId,
+            guardian: AccountId,
+            approvals: u8
+        }
+
+    /// Event emitted when the last supporting guardian withdraws from the recovery
+        #[ink(event)]
+        pub struct RecoveryCancelled {
+            #[ink(topic)]
+            team: AccountId
+        }
+
+    /// Event emitted when the team's admin changes through handover or recovery
+        #[ink(event)]
+        pub struct AdminChanged {
+            #[ink(topic)]
+            team: AccountId,
+            previous: AccountId,
+            new_admin: AccountId,
+            time: Timestamp
+        }
+
+    /// Event emitted when the team's key limit changes
+        #[ink(event)]
+        pub struct MaxKeysUpdated {
+            #[ink(topic)]
+            team: AccountId,
+            max_keys: u8
+        }
+
+    /// Event emitted when the owner changes the platform wide key ceiling
+        #[ink(event)]
+        pub struct KeyCeilingUpdated {
+            ceiling: u8
+        }
+
+    /// Event emitted when the default or a program's storage quota changes
+        #[ink(event)]
+        pub struct StorageQuotaUpdated {
+            program: Option<u32>,
+            quota: u32
+        }
+
+    /// Event emitted when legacy keys are moved to the indexed key storage
+        #[ink(event)]
+        pub struct KeysMigrated {
+            count: u32
+        }
+
+    /// Event emitted when legacy proposals are moved to the current project layout
+        #[ink(event)]
+        pub struct ProposalsMigrated {
+            count: u32
+        }
+
+    /// Event emitted when new grant Issuer is registered
+        #[ink(event)]
+        pub struct IssuerProfileCreated {
+            #[ink(topic)]
+            name: String,
+            id: u16,
+            account: AccountId,
+            time: Timestamp
+        }
+
+    /// Event emitted when an issuer publishes a grant program
+        #[ink(event)]
+        pub struct GrantProgramCreated {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32,
+            name: String,
+            open: Timestamp,
+            close: Timestamp
+        }
+
+    /// Event emitted when the program details are updated
+        #[ink(event)]
+        pub struct GrantProgramUpdated {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32
+        }
+
+    /// Event emitted when the program is closed by the issuer
+        #[ink(event)]
+        pub struct GrantProgramClosed {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32,
+            time: Timestamp
+        }
+
+    /// Event emitted when a proposal is attached to a grant program
+        #[ink(event)]
+        pub struct ProposalAttached {
+            #[ink(topic)]
+            program: u32,
+            #[ink(topic)]
+            owner: AccountId,
+            proposal: u8,
+            ask: Balance
+        }
+
+    /// Event emitted when an application is added to the issuer's queue
+        #[ink(event)]
+        pub struct ApplicationSubmitted {
+            #[ink(topic)]
+            issuer_id: u16,
+            #[ink(topic)]
+            applicant: AccountId,
+            id: u32,
+            proposal: u8,
+            time: Timestamp
+        }
+
+    /// Event emitted when the application file is updated
+        #[ink(event)]
+        pub struct ApplicationUpdated {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32,
+            file: String
+        }
+
+    /// Event emitted at each status transition of a proposal and its active application
+        #[ink(event)]
+        pub struct StatusChanged {
+            #[ink(topic)]
+            owner: AccountId,
+            proposal: u8,
+            application: Option<(u16,u32)>,
+            previous: Status,
+            status: Status,
+            time: Timestamp
+        }
+
+    /// Event emitted when the issuer sets its review rubric
+        #[ink(event)]
+        pub struct RubricUpdated {
+            #[ink(topic)]
+            issuer_id: u16,
+            criteria: Vec<Criterion>
+        }
+
+    /// Event emitted when reviewers are assigned to an application
+        #[ink(event)]
+        pub struct ReviewersAssigned {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32,
+            reviewers: Vec<AccountId>
+        }
+
+    /// Event emitted when a reviewer submits a review, the scores stay hidden until the decision
+        #[ink(event)]
+        pub struct ReviewSubmitted {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32,
+            #[ink(topic)]
+            reviewer: AccountId
+        }
+
+    /// Event emitted when a reviewer declares a conflict or recuses from an application
+        #[ink(event)]
+        pub struct ConflictDeclared {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32,
+            #[ink(topic)]
+            reviewer: AccountId,
+            recused: bool
+        }
+
+    /// Event emitted when the issuer publishes the decision of a reviewed application
+        #[ink(event)]
+        pub struct DecisionPublished {
+            #[ink(topic)]
+            issuer_id: u16,
+            id: u32,
+            status: Status,
+            score: Option<u32>
+        }
+
+    /// Event emitted when a project's milestones are pivoted
+        #[ink(event)]
+        pub struct MilestonePivoted {
+            #[ink(topic)]
+            owner: AccountId,
+            project: u8,
+            mile_no: u8,
+            reason: String
+        }
+
+    /// Event emitted when a team submits a milestone delivery
+        #[ink(event)]
+        pub struct MilestoneSubmitted {
+            #[ink(topic)]
+            owner: AccountId,
+            project: u8,
+            mile_no: u8,
+            evidence: String
+        }
+
+    /// Event emitted when a submitted milestone is evaluated
+        #[ink(event)]
+        pub struct MilestoneEvaluated {
+            #[ink(topic)]
+            owner: AccountId,
+            project: u8,
+            mile_no: u8,
+            #[ink(topic)]
+            evaluator: AccountId,
+            state: MilestoneState
+        }
+
+    /// Event emitted when an issuer deposits into a project's escrow
+        #[ink(event)]
+        pub struct EscrowDeposited {
+            #[ink(topic)]
+            owner: AccountId,
+            project: u8,
+            issuer_id: u16,
+            #[ink(topic)]
+            depositor: AccountId,
+            amount: Balance
+        }
+
+    /// Event emitted when an accepted milestone is paid from the escrow
+        #[ink(event)]
+        pub struct EscrowReleased {
+            #[ink(topic)]
+            owner: AccountId,
+            project: u8,
+            mile_no: u8,
+            amount: Balance
+        }
+
+    /// Event emitted when the issuer reclaims the unspent escrow
+        #[ink(event)]
+        pub struct EscrowReclaimed {
+            #[ink(topic)]
+            owner: AccountId,
+            project: u8,
+            #[ink(topic)]
+            to: AccountId,
+            amount: Balance
+        }
+
+    /// Event emitted when an action touches a milestone past its due date
+        #[ink(event)]
+        pub struct MilestoneOverdue {
+            #[ink(topic)]
+            owner: AccountId,
+            project: u8,
+            mile_no: u8,
+            due: Timestamp
+        }
+
+    /// Event for setting passcode
+        #[ink(event)]
+        pub struct PasscodeSet {
+            #[ink(topic)]
+            account: AccountId
+        }
+
+    /// Event emitted when an account is invited to a team
+        #[ink(event)]
+        pub struct MemberInvited {
+            #[ink(topic)]
+            team: AccountId,
+            #[ink(topic)]
+            account: AccountId,
+            role: MemberRole,
+            expires: Timestamp
+        }
+
+    /// Event emitted when an invited account declines to join a team
+        #[ink(event)]
+        pub struct InvitationDeclined {
+            #[ink(topic)]
+            team: AccountId,
+            account: AccountId
+        }
+
+    /// Event emitted when an expired invitation is dropped instead of being accepted
+        #[ink(event)]
+        pub struct InvitationLapsed {
+            #[ink(topic)]
+            team: AccountId,
+            account: AccountId
+        }
+
+    /// Event emitted when a member leaves or is removed from a team
+        #[ink(event)]
+        pub struct MemberRemoved {
+            #[ink(topic)]
+            team: AccountId,
+            #[ink(topic)]
+            member: AccountId,
+            by: AccountId,
+            time: Timestamp
+        }
+
+    /// Event for notifying a reference team link has been updated per individual profile
+        #[ink(event)]
+        pub struct UpdatedTeamMembership {
+            #[ink(topic)]
+            team_name: String,
+            team_id: AccountId,
+            individual_id: AccountId,
+            time: Timestamp
+        }
+
+
+    impl OrdumState {
+
+        #[ink(constructor)]
+        pub fn new() -> Self{
+                              
+                let mut owner = Lazy::new();
+                owner.set(&Self::env().caller());
+
+                Self {
+                    owner,
+                    key_ceiling: Lazy::new(),
+                    default_quota: Lazy::new(),
+                    individual_profile: Mapping::default(),
+                    all_individuals: vec![],
+                    team_applicant_profile: Mapping::default(),
+                    all_applicant_teams: vec![],
+                    manage_keys: vec![],
+                    key_index: Mapping::default(),
+                    key_wallets: Mapping::default(),
+                    pending_operations: Mapping::default(),
+                    operation_nonce: Mapping::default(),
+                    team_roles: Mapping::default(),
+                    invitations: Mapping::default(),
+                    invited: Mapping::default(),
+                    recovery_configs: Mapping::default(),
+                    admin_handovers: Mapping::default(),
+                    recovery_requests: Mapping::default(),
+                    delayed_operations: Mapping::default(),
+                    issuer_profile: Mapping::default(),
+                    issuer_ids: Mapping::default(),
+                    all_issuers: Lazy::new(),
+                    grant_programs: Mapping::default(),
+                    issuer_programs: Mapping::default(),
+                    program_count: Lazy::new(),
+                    proposal: Mapping::default(),
+                    db_auth: Mapping::default(),
+                    projects: Mapping::default(),
+                    archived_proposals: Mapping::default(),
+                    queue_applications: Mapping::default(),
+                    application_count: Mapping::default(),
+                    applicant_applications: Mapping::default(),
+                    rubrics: Mapping::default(),
+                    reviews: Mapping::default(),
+                    conflicts: Mapping::default(),
+                    escrows: Mapping::default()
+                }
+        }
+
+        // Remember Upgradability
+        /// Modifies the code which is used to execute calls to this contract address (`AccountId`).
+        ///
+        /// We use this to upgrade the contract logic. We don't do any authorization here, any caller
+        /// can execute this method. In a production contract you would do some authorization here.
+        #[ink(message, selector = 0xC0DE2000 )]
+        pub fn set_code(&mut self, code_hash: [u8; 32]) {
+            ink::env::set_code_hash(&code_hash).unwrap_or_else(|err| {
+                panic!(
+                    "Failed to `set_code_hash` to {:?} due to {:?}",
+                    code_hash, err
+                )
+            });
+            ink::env::debug_println!(" Switched code hash to {:?}.", code_hash);
+        }
+
+        /// Contracts upgraded from code without an owner are owned by `UPGRADE_OWNER`
+        fn is_owner(&self,account:AccountId) -> bool{
+            self.owner.get().unwrap_or_else(|| AccountId::from(UPGRADE_OWNER)) == account
+        }
+
+        fn key_ceiling(&self) -> u8{
+            self.key_ceiling.get().unwrap_or(DEFAULT_KEY_CEILING)
+        }
+
+        fn default_quota(&self) -> u32{
+            self.default_quota.get().unwrap_or(MAX_MEM)
+        }
+
+        /// Sets the platform wide ceiling of allowed keys per team, only the owner can set it.
+        /// Teams keep their current limit until they change it.
+        #[ink(message, selector = 0xC0DE2002 )]
+        pub fn set_key_ceiling(&mut self, ceiling: u8) -> CreateResult<()> {
+            if !self.is_owner(Self::env().caller()) {
+                return Err(Error::NotAuthorized)
+            }
+            self.key_ceiling.set(&ceiling);
+
+            Self::env().emit_event(KeyCeilingUpdated{
+                ceiling
+            });
+            Ok(())
+        }
+
+        /// Sets the default storage quota in bytes per project, only the owner can set it.
+        /// Programs with their own quota keep it.
+        #[ink(message, selector = 0xC0DE2003 )]
+        pub fn set_default_quota(&mut self, quota: u32) -> CreateResult<()> {
+            if !self.is_owner(Self::env().caller()) {
+                return Err(Error::NotAuthorized)
+            }
+            self.default_quota.set(&quota);
+
+            Self::env().emit_event(StorageQuotaUpdated{
+                program: None,
+                quota
+            });
+            Ok(())
+        }
+
+        /// One time migration of the `manage_keys` vector into the indexed key storage.
+        ///
+        /// Should be called right after `set_code`, calling it again does nothing as the
+        /// legacy vector is drained. Only the owner can run it, `UPGRADE_OWNER` is stored as the owner.
+        /// Migrated teams start with a threshold of 1 and the default key limit.
+        #[ink(message, selector = 0xC0DE2001 )]
+        pub fn migrate_keys(&mut self) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            if !self.is_owner(caller) {
+                return Err(Error::NotAuthorized)
+            }
+            if self.owner.get().is_none() {
+                self.owner.set(&caller);
+            }
+            let legacy = core::mem::take(&mut self.manage_keys);
+            let count = legacy.len() as u32;
+
+            legacy.into_iter().map(KeyManagement::from).for_each(|wallet|{
+                wallet.allowed_keys.iter().for_each(|key|{
+                    self.index_key(*key,wallet.key_pointer);
+                });
+                self.key_wallets.insert(wallet.key_pointer,&wallet);
+            });
+
+            Self::env().emit_event(KeysMigrated{
+                count
+            });
+            Ok(())
+        }
+
+        /// Migration of the legacy proposals of the `owners` into the current project layout.
+        ///
+        /// Should be called right after `set_code` with all the proposal owners, in batches if needed.
+        /// Owners without legacy proposals are skipped. Only the owner can run it.
+        /// Migrated projects are drafts without program, their milestones have no terms.
+        #[ink(message, selector = 0xC0DE2005 )]
+        pub fn migrate_proposals(&mut self,owners: Vec<AccountId>) -> CreateResult<()> {
+            if !self.is_owner(Self::env().caller()) {
+                return Err(Error::NotAuthorized)
+            }
+            let count = owners.into_iter().map(|owner| self.migrate_proposals_of(owner)).sum();
+
+            Self::env().emit_event(ProposalsMigrated{
+                count
+            });
+            Ok(())
+        }
 
         #[ink(message,selector=0xC0DE1001)]
         pub fn get_team_applicant_profile(&self,id:Option<AccountId>) -> CreateResult<TeamApplicantProfile>{
 
             if let Some(id_inner) = id {
 
-                if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                    key.allowed_keys.contains(&id_inner)
-                }){
+                if let Some(wallet) = self.wallet_of(id_inner,None){
                     let profile = self.team_applicant_profile.get(wallet.key_pointer)
                         .ok_or(Error::UnexpectedError)?;
 
@@ -784,9 +2734,7 @@ mod ordum {
 
                 let caller = Self::env().caller();
                 // Check if the caller is authorized to retrieve Applicant profile
-                if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                    key.allowed_keys.contains(&caller)
-                }){
+                if let Some(wallet) = self.authorize(caller,None,Capability::ViewPrivateData){
                     let profile = self.team_applicant_profile.get(wallet.key_pointer)
                         .ok_or(Error::UnexpectedError)?;
 
@@ -829,405 +2777,2564 @@ mod ordum {
         pub fn get_all_individuals(&self) -> CreateResult<Vec<(String, AccountId)>>{
             Ok(self.all_individuals.clone())
         }
-        
-      
+
+
+        #[ink(message, selector = 0xC0DE1013)]
+        pub fn get_issuer_profile(&self,id:u16) -> CreateResult<IssuerProfile>{
+            self.issuer_profile.get(id).ok_or(Error::ProfileDontExists)
+        }
+
+
+        #[ink(message, selector = 0xC0DE1014)]
+        pub fn get_all_issuers(&self) -> CreateResult<Vec<(String, u16)>>{
+            Ok(self.all_issuers.get().unwrap_or_default())
+        }
+
+
+        /// Grant programs of the issuer or all programs if no issuer is provided
+        #[ink(message, selector = 0xC0DE1015)]
+        pub fn get_programs(&self,issuer_id:Option<u16>) -> CreateResult<Vec<GrantProgram>>{
+            let programs = match issuer_id {
+                Some(id) => self.issuer_programs.get(id).unwrap_or_default(),
+                None => (1..=self.program_count.get().unwrap_or_default()).collect()
+            };
+
+            Ok(programs.into_iter().filter_map(|id| self.grant_programs.get(id)).collect())
+        }
+
+
+        #[ink(message, selector = 0xC0DE1016)]
+        pub fn get_program(&self,id:u32) -> CreateResult<GrantProgram>{
+            self.grant_programs.get(id).ok_or(Error::ProgramNotFound)
+        }
+
+
+        /// Application queue of the issuer, only visible to the issuer's allowed keys
+        /// `status` filters the applications, all are returned if not provided
+        #[ink(message, selector = 0xC0DE1017)]
+        pub fn get_issuer_applications(&self,issuer_id:u16,status:Option<Status>) -> CreateResult<Vec<Application>>{
+            self.authorize_issuer(Self::env().caller(),issuer_id)?;
+
+            let count = self.application_count.get(issuer_id).unwrap_or_default();
+            Ok((1..=count).filter_map(|id| self.queue_applications.get((issuer_id,id)))
+                .filter(|application| status.as_ref().map_or(true,|status| application.status == *status))
+                .collect())
+        }
+
+
+        /// Applications of the team or of the caller's individual profile
+        #[ink(message, selector = 0xC0DE1018)]
+        pub fn get_my_applications(&self,team:Option<AccountId>,status:Option<Status>) -> CreateResult<Vec<Application>>{
+            let applicant = self.applicant_of(Self::env().caller(),team,Capability::ViewPrivateData)?;
+
+            Ok(self.applicant_applications.get(applicant).unwrap_or_default().into_iter()
+                .filter_map(|key| self.queue_applications.get(key))
+                .filter(|application| status.as_ref().map_or(true,|status| application.status == *status))
+                .collect())
+        }
+
+
+        /// Proposals of the team or of the caller's individual profile with the given `status`
+        #[ink(message, selector = 0xC0DE1020)]
+        pub fn get_proposals_by_status(&self,team:Option<AccountId>,status:Status) -> CreateResult<Vec<Project>>{
+            let owner = self.applicant_of(Self::env().caller(),team,Capability::ViewPrivateData)?;
+
+            Ok(self.projects.get(owner).unwrap_or_default().into_iter()
+                .filter(|project| project.status == status)
+                .collect())
+        }
+
+
+        /// Single application, visible to the applicant and the issuer
+        #[ink(message, selector = 0xC0DE1019)]
+        pub fn get_application(&self,issuer_id:u16,application_id:u32) -> CreateResult<Application>{
+            let caller = Self::env().caller();
+            let application = self.queue_applications.get((issuer_id,application_id)).ok_or(Error::ApplicationNotFound)?;
+
+            if !self.is_applicant(caller,application.applicant,Capability::ViewPrivateData)
+                && self.authorize_issuer(caller,issuer_id).is_err() {
+                return Err(Error::NotAuthorized)
+            }
+            Ok(application)
+        }
+
+
+        /// Rubric of the issuer
+        #[ink(message, selector = 0xC0DE1022)]
+        pub fn get_rubric(&self,issuer_id:u16) -> CreateResult<Vec<Criterion>>{
+            self.rubrics.get(issuer_id).ok_or(Error::RubricNotFound)
+        }
+
+
+        /// Review round of the application.
+        /// The issuer sees all reviews, a reviewer only its own and the applicant all once the decision is published
+        #[ink(message, selector = 0xC0DE1021)]
+        pub fn get_reviews(&self,issuer_id:u16,application_id:u32) -> CreateResult<ReviewRound>{
+            let caller = Self::env().caller();
+            let application = self.queue_applications.get((issuer_id,application_id)).ok_or(Error::ApplicationNotFound)?;
+            let mut round = self.reviews.get((issuer_id,application_id)).ok_or(Error::NotReviewer)?;
+
+            if self.authorize_issuer(caller,issuer_id).is_ok() {
+                return Ok(round)
+            }
+            if self.is_applicant(caller,application.applicant,Capability::ViewPrivateData) {
+                return if round.decision.is_some() { Ok(round) } else { Err(Error::ReviewsHidden) }
+            }
+            if round.reviewers.contains(&caller) {
+                round.reviews.retain(|review| review.reviewer == caller);
+                return Ok(round)
+            }
+            Err(Error::NotAuthorized)
+        }
+
+
+        /// Conflicts declared by reviewers on the application, only visible to the issuer's allowed keys
+        #[ink(message, selector = 0xC0DE1023)]
+        pub fn get_conflicts(&self,issuer_id:u16,application_id:u32) -> CreateResult<Vec<Conflict>>{
+            self.authorize_issuer(Self::env().caller(),issuer_id)?;
+            Ok(self.conflicts.get((issuer_id,application_id)).unwrap_or_default())
+        }
+
+
+        /// Used and available storage in bytes of the project
+        #[ink(message, selector = 0xC0DE1024)]
+        pub fn get_storage_usage(&self,team:Option<AccountId>,project_id:u8) -> MilestoneResult<(u32,u32)>{
+            let owner = self.owner_of(Self::env().caller(),team,Capability::ViewPrivateData)?;
+            let projects = self.projects.get(owner).ok_or(MilestoneError::ProjectNotFound)?;
+            let project = (project_id as usize).checked_sub(1)
+                .and_then(|index| projects.get(index))
+                .ok_or(MilestoneError::ProjectNotFound)?;
+
+            Ok((project.total_mem,self.quota_of(project.program)))
+        }
+
+
+        /// Overdue milestones `(project, milestone)` of the team or individual, of all projects if none is given
+        #[ink(message, selector = 0xC0DE1025)]
+        pub fn get_overdue_milestones(&self,team:Option<AccountId>,project_id:Option<u8>) -> MilestoneResult<Vec<(u8,AddMilestone)>>{
+            let owner = self.owner_of(Self::env().caller(),team,Capability::ViewPrivateData)?;
+            let now = Self::env().block_timestamp();
+
+            Ok(self.projects.get(owner).unwrap_or_default().iter()
+                .filter(|project| project_id.map_or(true,|id| project.id == id))
+                .flat_map(|project| project.overdue(now).into_iter().map(move |mile| (project.id,mile)))
+                .collect())
+        }
+
+
+        /// Overdue milestones `(owner, project, milestone)` of the projects attached to the program,
+        /// only visible to the issuer's allowed keys
+        #[ink(message, selector = 0xC0DE1026)]
+        pub fn get_program_overdue(&self,program_id:u32) -> CreateResult<Vec<(AccountId,u8,AddMilestone)>>{
+            let program = self.grant_programs.get(program_id).ok_or(Error::ProgramNotFound)?;
+            self.authorize_issuer(Self::env().caller(),program.issuer_id)?;
+            let now = Self::env().block_timestamp();
+
+            Ok(program.proposals.iter()
+                .filter_map(|(owner,proposal)| {
+                    let project = self.projects.get(owner)?.into_iter().find(|project| project.id == *proposal)?;
+                    Some((*owner,project))
+                })
+                .flat_map(|(owner,project)| {
+                    let id = project.id;
+                    project.overdue(now).into_iter().map(move |mile| (owner,id,mile))
+                })
+                .collect())
+        }
+
+
+        /// Escrow of the `owner`'s project
+        #[ink(message, selector = 0xC0DE1027)]
+        pub fn get_escrow(&self,owner:AccountId,proposal_id:u8) -> Option<Escrow>{
+            self.escrows.get((owner,proposal_id))
+        }
+
+
+        #[ink(message, selector = 0xC0DE1005)]
+        pub fn get_archived_proposals(&self,id:AccountId) -> CreateResult<Vec<Project>>{
+            self.archived_proposals.get(id).ok_or(Error::AccountDontExists)
+        }
+
+
+        /// All the teams the caller belongs to, either as an allowed key or as a member
+        #[ink(message, selector = 0xC0DE1006)]
+        pub fn get_my_teams(&self) -> CreateResult<Vec<(AccountId,MemberRole)>>{
+            let caller = Self::env().caller();
+            let mut teams: Vec<(AccountId,MemberRole)> = vec![];
+
+            // Allowed keys are privileged accounts of the team
+            self.key_index.get(caller).unwrap_or_default().into_iter().for_each(|key_pointer|{
+                teams.push((key_pointer,MemberRole::Admin));
+            });
+
+            // Members referenced in the individual profile
+            if let Some(profile) = self.individual_profile.get(caller){
+                profile.ref_team.into_iter().for_each(|team|{
+                    if !teams.iter().any(|t| t.0 == team.0){
+                        teams.push(team);
+                    }
+                });
+            }
+
+            Ok(teams)
+        }
+
+
+        #[ink(message, selector = 0xC0DE1007)]
+        pub fn get_pending_operations(&self,team:AccountId) -> CreateResult<Vec<PendingOperation>>{
+            Ok(self.pending_operations.get(team).unwrap_or_default())
+        }
+
+
+        #[ink(message, selector = 0xC0DE1008)]
+        pub fn get_team_roles(&self,team:AccountId) -> CreateResult<Vec<TeamRole>>{
+            Ok(self.team_roles.get(team).unwrap_or_default())
+        }
+
+
+        /// Number of keys the team can still add
+        #[ink(message, selector = 0xC0DE1012)]
+        pub fn get_key_capacity(&self,team:AccountId) -> CreateResult<u8>{
+            let wallet = self.key_wallets.get(team).ok_or(Error::ProfileDontExists)?;
+            Ok(wallet.remaining_keys())
+        }
+
+
+        #[ink(message, selector = 0xC0DE1010)]
+        pub fn get_admin_handover(&self,team:AccountId) -> CreateResult<AdminHandover>{
+            self.admin_handovers.get(team).ok_or(Error::HandoverNotFound)
+        }
+
+
+        #[ink(message, selector = 0xC0DE1011)]
+        pub fn get_recovery(&self,team:AccountId) -> CreateResult<(RecoveryConfig,Option<RecoveryRequest>)>{
+            Ok((self.recovery_configs.get(team).unwrap_or_default(),self.recovery_requests.get(team)))
+        }
+
+
+        #[ink(message, selector = 0xC0DE1009)]
+        pub fn get_invitations(&self) -> CreateResult<Vec<Invitation>>{
+            let caller = Self::env().caller();
+            Ok(self.invitations.get(caller).unwrap_or_default())
+        }
+
+
+        /// Role of the `account` in the team, allowed keys act as admins
+        fn member_role(&self,wallet:&KeyManagement,account:AccountId) -> Option<MemberRole>{
+            if wallet.admin == account || wallet.allowed_keys.contains(&account) {
+                return Some(MemberRole::Admin)
+            }
+            self.team_applicant_profile.get(wallet.key_pointer)?.member_role(account)
+        }
+
+        fn has_capability(&self,wallet:&KeyManagement,account:AccountId,role:&MemberRole,capability:&Capability) -> bool{
+            match (capability,role) {
+                (Capability::ManageKeys,_) => wallet.admin == account,
+                (Capability::ApproveOperations,_) => wallet.allowed_keys.contains(&account),
+                (_,MemberRole::Admin) => true,
+                (_,MemberRole::Regular) => REGULAR_CAPABILITIES.contains(capability),
+                (_,MemberRole::Custom(id)) => self.team_roles.get(wallet.key_pointer).unwrap_or_default().iter()
+                    .find(|team_role| team_role.id == *id)
+                    .map_or(false, |team_role| team_role.capabilities.contains(capability))
+            }
+        }
+
+        /// Key pointers of the teams the `account` belongs to, as an allowed key first and then as a member
+        fn teams_of(&self,account:AccountId) -> Vec<AccountId>{
+            let mut teams = self.key_index.get(account).unwrap_or_default();
+            if let Some(profile) = self.individual_profile.get(account){
+                profile.ref_team.iter().for_each(|(team,_)|{
+                    if !teams.contains(team){
+                        teams.push(*team);
+                    }
+                });
+            }
+            teams
+        }
+
+        /// Team key management of the team where the `account` has the `capability`.
+        /// If `team` is not specified the first team where the account has the capability is used.
+        fn authorize(&self,account:AccountId,team:Option<AccountId>,capability:Capability) -> Option<KeyManagement>{
+            let key_pointer = match team {
+                Some(team_inner) => team_inner,
+                None => return self.teams_of(account).into_iter()
+                    .find_map(|key_pointer| self.authorize(account,Some(key_pointer),capability.clone()))
+            };
+
+            let wallet = self.key_wallets.get(key_pointer)?;
+            let role = self.member_role(&wallet,account)?;
+            if self.has_capability(&wallet,account,&role,&capability) {
+                Some(wallet)
+            }else{
+                None
+            }
+        }
+
+
+        /// Team key management which the `account` is an allowed key of.
+        /// If `team` is not specified the first team the account belongs to is used.
+        fn wallet_of(&self,account:AccountId,team:Option<AccountId>) -> Option<KeyManagement>{
+            let key_pointer = match team {
+                Some(team_inner) => team_inner,
+                None => *self.key_index.get(account)?.first()?
+            };
+
+            let wallet = self.key_wallets.get(key_pointer)?;
+            if wallet.allowed_keys.contains(&account) {
+                Some(wallet)
+            }else{
+                None
+            }
+        }
+
+        fn index_key(&mut self,key:AccountId,key_pointer:AccountId){
+            let mut teams = self.key_index.get(key).unwrap_or_default();
+            if !teams.contains(&key_pointer){
+                teams.push(key_pointer);
+                self.key_index.insert(key,&teams);
+            }
+        }
+
+        fn unindex_key(&mut self,key:AccountId,key_pointer:AccountId){
+            let mut teams = self.key_index.get(key).unwrap_or_default();
+            teams.retain(|team| *team != key_pointer);
+            if teams.is_empty(){
+                self.key_index.remove(key);
+            }else{
+                self.key_index.insert(key,&teams);
+            }
+        }
+
+        /// Stores the team key management and indexes all of its allowed keys
+        fn register_wallet(&mut self,wallet:&KeyManagement) -> CreateResult<()>{
+            if self.key_wallets.contains(wallet.key_pointer){
+                return Err(Error::AccountExists)
+            }
+
+            wallet.allowed_keys.iter().for_each(|key|{
+                self.index_key(*key,wallet.key_pointer);
+            });
+            self.key_wallets.insert(wallet.key_pointer,wallet);
+            Ok(())
+        }
+
+        /// Removes the team key management and all of its indexed keys
+        fn unregister_wallet(&mut self,wallet:&KeyManagement){
+            wallet.allowed_keys.iter().for_each(|key|{
+                self.unindex_key(*key,wallet.key_pointer);
+            });
+            self.key_wallets.remove(wallet.key_pointer);
+        }
+
+        /// Applies the key action on the team's key management and keeps the key index in sync
+        fn apply_key_action(&mut self,key_pointer:AccountId,account:AccountId,action:KeyAction) -> CreateResult<()>{
+            let time = Self::env().block_timestamp();
+            let mut acc_manage = self.key_wallets.get(key_pointer).ok_or(Error::NotAuthorized)?;
+
+            acc_manage.update_keys_inner(account,action.clone())?;
+
+            // Keep the key index in sync
+            match action {
+                KeyAction::ADD => {
+                    self.index_key(account,key_pointer);
+                },
+                KeyAction::REMOVE => {
+                    self.unindex_key(account,key_pointer);
+                },
+                KeyAction::ChangeAdmin => {}
+            }
+            self.key_wallets.insert(key_pointer,&acc_manage);
+
+            Self::env().emit_event(KeysUpdated{
+                team: key_pointer,
+                action,
+                account,
+                time
+            });
+
+            Ok(())
+        }
+
+        /// Stores the operation until enough allowed keys approve it, the proposer being the first approval
+        fn queue_operation(&mut self,wallet:&KeyManagement,proposer:AccountId,operation:TeamOperation){
+            let time = Self::env().block_timestamp();
+            let id = self.next_operation_id(wallet.key_pointer);
+
+            let mut operations = self.pending_operations.get(wallet.key_pointer).unwrap_or_default();
+            operations.push(PendingOperation{
+                id,
+                proposer,
+                operation: operation.clone(),
+                approvals: vec![proposer],
+                created: time
+            });
+
+            self.pending_operations.insert(wallet.key_pointer,&operations);
+
+            Self::env().emit_event(OperationProposed{
+                team: wallet.key_pointer,
+                id,
+                proposer,
+                operation,
+                time
+            });
+        }
+
+        /// Id of the team's next pending or delayed operation
+        fn next_operation_id(&mut self,key_pointer:AccountId) -> u32{
+            let id = self.operation_nonce.get(key_pointer).unwrap_or_default().saturating_add(1);
+            self.operation_nonce.insert(key_pointer,&id);
+            id
+        }
+
+        /// Whether the approved operation has to wait out the handover delay.
+        /// Removing keys and weakening the recovery settings would let a compromised admin
+        /// get rid of the accounts able to veto a handover.
+        fn is_time_locked(&self,key_pointer:AccountId,operation:&TeamOperation) -> bool{
+            let config = self.recovery_configs.get(key_pointer).unwrap_or_default();
+            if config.handover_delay == 0 {
+                return false
+            }
+            match operation {
+                TeamOperation::RemoveKey(_) => true,
+                TeamOperation::RemoveMember(account) => self.key_wallets.get(key_pointer)
+                    .map_or(false,|wallet| wallet.allowed_keys.contains(account)),
+                TeamOperation::SetRecoveryConfig(new) => config.weakened_by(new),
+                _ => false
+            }
+        }
+
+        /// Executes an approved team operation `proposer` asked for, time locked operations are delayed
+        fn execute_operation(&mut self,key_pointer:AccountId,proposer:AccountId,operation:TeamOperation) -> CreateResult<()>{
+            if !self.is_time_locked(key_pointer,&operation) {
+                return self.apply_operation(key_pointer,proposer,operation)
+            }
+
+            let config = self.recovery_configs.get(key_pointer).unwrap_or_default();
+            let executable_at = Self::env().block_timestamp().saturating_add(config.handover_delay);
+            let id = self.next_operation_id(key_pointer);
+
+            let mut delayed = self.delayed_operations.get(key_pointer).unwrap_or_default();
+            delayed.push(DelayedOperation{
+                id,
+                proposer,
+                operation: operation.clone(),
+                executable_at
+            });
+            self.delayed_operations.insert(key_pointer,&delayed);
+
+            Self::env().emit_event(OperationDelayed{
+                team: key_pointer,
+                id,
+                operation,
+                executable_at
+            });
+            Ok(())
+        }
+
+        fn apply_operation(&mut self,key_pointer:AccountId,proposer:AccountId,operation:TeamOperation) -> CreateResult<()>{
+            match operation {
+                TeamOperation::SubmitProposal { chain, ref_no, file, mem } => {
+                    self.add_team_proposal(key_pointer,chain,ref_no,file,mem)
+                        .map_err(Error::Milestone)
+                },
+                TeamOperation::PivotMilestone { project, mile_no, reason, file, mem, terms } => {
+                    self.pivot(key_pointer,project,mile_no,reason,file,mem,terms)
+                        .map_err(Error::Milestone)
+                },
+                TeamOperation::ChangeAdmin(account) => {
+                    self.start_handover(key_pointer,proposer,account)
+                },
+                TeamOperation::RemoveKey(account) => {
+                    self.apply_key_action(key_pointer,account,KeyAction::REMOVE)
+                },
+                TeamOperation::RemoveMember(account) => {
+                    self.remove_from_team(key_pointer,account)
+                },
+                TeamOperation::SetRecoveryConfig(config) => {
+                    config.validate()?;
+                    self.recovery_configs.insert(key_pointer,&config);
+
+                    Self::env().emit_event(RecoveryConfigUpdated{
+                        team: key_pointer,
+                        config
+                    });
+                    Ok(())
+                },
+                TeamOperation::AttachProposal { project, program, ask } => {
+                    self.attach(key_pointer,project,program,ask)
+                },
+                TeamOperation::SetThreshold(threshold) => {
+                    let mut wallet = self.key_wallets.get(key_pointer).ok_or(Error::NotAuthorized)?;
+                    wallet.update_threshold(threshold)?;
+                    self.key_wallets.insert(key_pointer,&wallet);
+
+                    Self::env().emit_event(ThresholdUpdated{
+                        team: key_pointer,
+                        threshold
+                    });
+                    Ok(())
+                }
+            }
+        }
+
+        /// Stores a new proposal for the team
+        fn add_team_proposal(&mut self,key_pointer:AccountId,chain:Chains,ref_no:Option<u32>,file:String,mem:u32) -> MilestoneResult<()>{
+
+            self.store_proposal(key_pointer,chain,ref_no,file,mem)?;
+
+            // update Team application count
+            let mut team_profile = self.team_applicant_profile.get(key_pointer).ok_or(MilestoneError::NotAuthorized)?;
+            team_profile.update_applications_count();
+            self.team_applicant_profile.insert(key_pointer,&team_profile);
+
+            Ok(())
+        }
+
+        /// Appends a new proposal to the `owner`'s projects
+        fn store_proposal(&mut self,owner:AccountId,chain:Chains,ref_no:Option<u32>,file:String,mem:u32) -> MilestoneResult<()>{
+            // New ids follow the owner's legacy proposals
+            self.migrate_proposals_of(owner);
+            let mut projects = self.projects.get(owner).unwrap_or_default();
+            if projects.len() >= u8::MAX as usize {
+                return Err(MilestoneError::MaxProjectsExceeded)
+            }
+
+            // Build the Project Object and InnerProject
+            let inner_project = InnerProject {
+                chain,
+                file,
+                referenda_no:ref_no
+            };
+            // New projects are not attached to a program yet
+            let mut project = Project::new(projects.len() as u8,inner_project,0);
+            project.charge(mem,0,self.quota_of(None))?;
+
+            projects.push(project);
+            self.projects.insert(owner,&projects);
+            Ok(())
+        }
+
+        /// Moves the `owner`'s legacy proposals into `projects`, returns the number of moved proposals
+        fn migrate_proposals_of(&mut self,owner:AccountId) -> u32{
+            let legacy = match self.proposal.take(owner) {
+                Some(legacy) => legacy,
+                None => return 0
+            };
+            let count = legacy.len() as u32;
+
+            let mut projects: Vec<Project> = legacy.into_iter().map(Project::from).collect();
+            projects.extend(self.projects.get(owner).unwrap_or_default());
+            self.projects.insert(owner,&projects);
+            count
+        }
+
+        /// Applies `update` on the `owner`'s project with its storage quota and writes the project back
+        fn update_project<F>(&mut self,owner:AccountId,project_id:u8,update:F) -> MilestoneResult<()>
+        where F: FnOnce(&mut Project,u32) -> MilestoneResult<()>
+        {
+            let mut projects = self.projects.get(owner).ok_or(MilestoneError::ProjectNotFound)?;
+            let project = (project_id as usize).checked_sub(1)
+                .and_then(|index| projects.get_mut(index))
+                .ok_or(MilestoneError::ProjectNotFound)?;
+
+            let quota = self.quota_of(project.program);
+            update(project,quota)?;
+            self.projects.insert(owner,&projects);
+            Ok(())
+        }
+
+        /// Pays the accepted milestone's `amount` from the project's escrow to the project owner
+        /// Projects attached to a program are paid up to their approved `ask`, the others up to the issuer's deposit.
+        /// Only the issuer which funded the escrow can release from it.
+        fn release(&mut self,owner:AccountId,project_id:u8,mile_no:u8,amount:Balance,ask:Option<Balance>,issuer_id:u16) -> MilestoneResult<()>{
+            // Projects without escrow are funded outside the contract
+            let mut escrow = match self.escrows.get((owner,project_id)) {
+                Some(escrow) if amount > 0 => escrow,
+                _ => return Ok(())
+            };
+            if escrow.issuer_id != issuer_id {
+                return Err(MilestoneError::NotAuthorized)
+            }
+            if ask.map_or(false,|ask| escrow.released.saturating_add(amount) > ask) {
+                return Err(MilestoneError::AskExceeded)
+            }
+            if escrow.remaining() < amount {
+                return Err(MilestoneError::InsufficientEscrow)
+            }
+            escrow.released = escrow.released.saturating_add(amount);
+            self.escrows.insert((owner,project_id),&escrow);
+
+            Self::env().transfer(owner,amount).map_err(|_| MilestoneError::TransferFailed)?;
+
+            Self::env().emit_event(EscrowReleased{
+                owner,
+                project: project_id,
+                mile_no,
+                amount
+            });
+            Ok(())
+        }
+
+        /// Emits `MilestoneOverdue` when the milestone touched by an action is late
+        fn notify_overdue(owner:AccountId,project:u8,milestone:&AddMilestone){
+            if milestone.is_overdue(Self::env().block_timestamp()) {
+                Self::env().emit_event(MilestoneOverdue{
+                    owner,
+                    project,
+                    mile_no: milestone.main_index,
+                    due: milestone.due
+                });
+            }
+        }
+
+        /// Storage quota of a project, the program's quota if attached to a program with its own quota
+        fn quota_of(&self,program:Option<u32>) -> u32{
+            program.and_then(|id| self.grant_programs.get(id))
+                .and_then(|program| program.quota)
+                .unwrap_or(self.default_quota())
+        }
+
+        /// Owner of the projects the `account` acts on, the team where it has the `capability`
+        /// or else its individual profile
+        fn owner_of(&self,account:AccountId,team:Option<AccountId>,capability:Capability) -> MilestoneResult<AccountId>{
+            if let Some(wallet) = self.authorize(account,team,capability){
+                Ok(wallet.key_pointer)
+            }else if team.is_some(){
+                // The caller is not allowed on the specified team
+                Err(MilestoneError::NotAuthorized)
+            }else if self.individual_profile.contains(account){
+                Ok(account)
+            }else{
+                Err(MilestoneError::NotAuthorized)
+            }
+        }
+
+        /// Issuer profile which the `account` is an allowed key of
+        fn authorize_issuer(&self,account:AccountId,issuer_id:u16) -> CreateResult<IssuerProfile>{
+            let issuer = self.issuer_profile.get(issuer_id).ok_or(Error::ProfileDontExists)?;
+            if !issuer.is_allowed(&account) {
+                return Err(Error::NotAuthorized)
+            }
+            Ok(issuer)
+        }
+
+        /// Team where the `account` has the `capability` or else the account itself as an individual
+        fn applicant_of(&self,account:AccountId,team:Option<AccountId>,capability:Capability) -> CreateResult<AccountId>{
+            match self.authorize(account,team,capability) {
+                Some(wallet) => Ok(wallet.key_pointer),
+                None if team.is_none() => Ok(account),
+                None => Err(Error::NotAuthorized)
+            }
+        }
+
+        /// Moves the `owner`'s proposal and its active application to the new status
+        fn change_status(&mut self,owner:AccountId,proposal_id:u8,status:Status,actor:Actor) -> ApplicationResult<()>{
+            let time = Self::env().block_timestamp();
+            let mut projects = self.projects.get(owner).ok_or(Error::ProposalNotFound)?;
+            let project = projects.iter_mut().find(|project| project.id == proposal_id).ok_or(Error::ProposalNotFound)?;
+
+            let previous = project.transition(status.clone(),&actor)?;
+            let application_key = project.application;
+            self.projects.insert(owner,&projects);
+
+            if let Some(key) = application_key {
+                let mut application = self.queue_applications.get(key).ok_or(Error::ApplicationNotFound)?;
+                application.status = status.clone();
+                application.updated = time;
+                self.queue_applications.insert(key,&application);
+            }
+
+            Self::env().emit_event(StatusChanged{
+                owner,
+                proposal: proposal_id,
+                application: application_key,
+                previous,
+                status,
+                time
+            });
+            Ok(())
+        }
+
+        /// Whether the `reviewer` can't review the `applicant`'s application `key`.
+        /// The applicant itself, its keys, its members and reviewers who recused are conflicted.
+        fn is_conflicted(&self,reviewer:AccountId,applicant:AccountId,key:(u16,u32)) -> bool{
+            if reviewer == applicant {
+                return true
+            }
+            let on_team = self.individual_profile.get(reviewer)
+                .map_or(false,|profile| profile.ref_team.iter().any(|(team,_)| *team == applicant));
+            let is_member = self.key_wallets.get(applicant)
+                .map_or(false,|wallet| self.member_role(&wallet,reviewer).is_some());
+            let recused = self.conflicts.get(key).unwrap_or_default().iter()
+                .any(|conflict| conflict.reviewer == reviewer && conflict.recused);
+
+            on_team || is_member || recused
+        }
+
+        /// Records the conflict of the reviewer on the application, one per reviewer
+        fn add_conflict(&mut self,key:(u16,u32),reviewer:AccountId,reason:String,recused:bool) -> ApplicationResult<()>{
+            if reason.len() > MAX_REASON_LEN as usize {
+                return Err(Error::ReasonTooLong)
+            }
+            let mut conflicts = self.conflicts.get(key).unwrap_or_default();
+            let conflict = Conflict{
+                reviewer,
+                reason,
+                declared: Self::env().block_timestamp(),
+                recused
+            };
+
+            // A recusal is kept when the reviewer declares again
+            match conflicts.iter().position(|conflict| conflict.reviewer == reviewer) {
+                Some(index) => {
+                    let recused = conflicts[index].recused || recused;
+                    conflicts[index] = Conflict{ recused, ..conflict }
+                },
+                None if conflicts.len() < MAX_CONFLICTS as usize => conflicts.push(conflict),
+                None => return Err(Error::MaxConflictsExceeded)
+            }
+            self.conflicts.insert(key,&conflicts);
+
+            Self::env().emit_event(ConflictDeclared{
+                issuer_id: key.0,
+                id: key.1,
+                reviewer,
+                recused
+            });
+            Ok(())
+        }
+
+        /// Records the issuer's decision on the application's review round and publishes its reviews
+        fn record_decision(&mut self,key:(u16,u32),status:Status){
+            if !matches!(status,Status::Approved | Status::Rejected | Status::ChangesRequested) {
+                return
+            }
+            if let Some(mut round) = self.reviews.get(key) {
+                let score = round.aggregate_score();
+                round.decision = Some(Decision{
+                    status: status.clone(),
+                    score,
+                    time: Self::env().block_timestamp()
+                });
+                self.reviews.insert(key,&round);
+
+                Self::env().emit_event(DecisionPublished{
+                    issuer_id: key.0,
+                    id: key.1,
+                    status,
+                    score
+                });
+            }
+        }
+
+        /// Whether the `account` acts for the `applicant`, being the individual itself or a team member with the `capability`
+        fn is_applicant(&self,account:AccountId,applicant:AccountId,capability:Capability) -> bool{
+            account == applicant || self.authorize(account,Some(applicant),capability).is_some()
+        }
+
+        /// Attaches the `owner`'s proposal to the program if it is open and the proposal is eligible
+        fn attach(&mut self,owner:AccountId,proposal_id:u8,program_id:u32,ask:Balance) -> ApplicationResult<()>{
+            let now = Self::env().block_timestamp();
+            let mut program = self.grant_programs.get(program_id).ok_or(Error::ProgramNotFound)?;
+            if !program.is_open(now) {
+                return Err(Error::ProgramClosed)
+            }
+
+            let mut projects = self.projects.get(owner).ok_or(Error::ProposalNotFound)?;
+            let project = projects.iter_mut().find(|project| project.id == proposal_id).ok_or(Error::ProposalNotFound)?;
+            if project.program.is_some() {
+                return Err(Error::ProposalAlreadyAttached)
+            }
+            let quota = program.quota.unwrap_or(self.default_quota());
+            if !program.chains.contains(&project.data.chain) || ask > program.max_ask || ask > program.budget || project.total_mem > quota {
+                return Err(Error::NotEligible)
+            }
+            // Programs without categories are open to all of them
+            let categories = self.categories_of(owner);
+            if !program.categories.is_empty() && !categories.iter().any(|category| program.categories.contains(category)) {
+                return Err(Error::NotEligible)
+            }
+
+            project.program = Some(program_id);
+            project.ask = ask;
+            program.proposals.push((owner,proposal_id));
+
+            self.projects.insert(owner,&projects);
+            self.grant_programs.insert(program_id,&program);
+
+            Self::env().emit_event(ProposalAttached{
+                program: program_id,
+                owner,
+                proposal: proposal_id,
+                ask
+            });
+            Ok(())
+        }
+
+        /// Categories of the team profile or else of the individual profile of the `owner`
+        fn categories_of(&self,owner:AccountId) -> Vec<Categories>{
+            match self.team_applicant_profile.get(owner) {
+                Some(team) => team.categories,
+                None => self.individual_profile.get(owner).map(|individual| individual.categories).unwrap_or_default()
+            }
+        }
+
+        /// Pivots the milestones of the `owner`'s project from `mile_no`
+        fn pivot(&mut self,owner:AccountId,project_id:u8,mile_no:u8,reason:String,file:String,mem:u32,terms:MilestoneTerms) -> MilestoneResult<()>{
+            let milestone = AddMilestone::new(mile_no, 0, file, mem, terms);
+            self.update_project(owner,project_id,|project,quota|{
+                project.pivot(mile_no,reason.clone(),milestone.clone(),mem,quota)
+            })?;
+            Self::notify_overdue(owner,project_id,&milestone);
+
+            Self::env().emit_event(MilestonePivoted{
+                owner,
+                project: project_id,
+                mile_no,
+                reason
+            });
+            Ok(())
+        }
+
+        /// Starts the time locked handover to the new admin
+        fn start_handover(&mut self,key_pointer:AccountId,proposer:AccountId,new_admin:AccountId) -> CreateResult<()>{
+            let time = Self::env().block_timestamp();
+            let config = self.recovery_configs.get(key_pointer).unwrap_or_default();
+
+            let executable_at = time.saturating_add(config.handover_delay);
+            self.admin_handovers.insert(key_pointer,&AdminHandover{
+                new_admin,
+                proposed_by: proposer,
+                executable_at
+            });
+
+            Self::env().emit_event(AdminHandoverProposed{
+                team: key_pointer,
+                new_admin,
+                executable_at
+            });
+
+            Ok(())
+        }
+
+        /// The team's recovery request if it has not lapsed
+        fn active_recovery(&self,key_pointer:AccountId,time:Timestamp) -> Option<RecoveryRequest>{
+            self.recovery_requests.get(key_pointer).filter(|request| time < request.expires)
+        }
+
+        /// Makes the account the team's admin and grants it a key
+        fn change_admin(&mut self,key_pointer:AccountId,new_admin:AccountId) -> CreateResult<()>{
+            let time = Self::env().block_timestamp();
+            let wallet = self.key_wallets.get(key_pointer).ok_or(Error::ProfileDontExists)?;
+
+            if !wallet.allowed_keys.contains(&new_admin){
+                if wallet.allowed_keys.contains(&wallet.admin){
+                    // The previous admin's key is handed over, adding would fail on a full team
+                    // and leave a lost or compromised key behind
+                    self.swap_key(key_pointer,wallet.admin,new_admin)?;
+                }else{
+                    self.apply_key_action(key_pointer,new_admin,KeyAction::ADD)?;
+                }
+            }
+            self.apply_key_action(key_pointer,new_admin,KeyAction::ChangeAdmin)?;
+
+            // Any pending appointment is outdated
+            self.admin_handovers.remove(key_pointer);
+            self.recovery_requests.remove(key_pointer);
+
+            Self::env().emit_event(AdminChanged{
+                team: key_pointer,
+                previous: wallet.admin,
+                new_admin,
+                time
+            });
+
+            Ok(())
+        }
+
+        /// Replaces the allowed key `old` with `new`, the number of keys stays the same
+        fn swap_key(&mut self,key_pointer:AccountId,old:AccountId,new:AccountId) -> CreateResult<()>{
+            let time = Self::env().block_timestamp();
+            let mut wallet = self.key_wallets.get(key_pointer).ok_or(Error::ProfileDontExists)?;
+            let index = wallet.allowed_keys.iter().position(|key| *key == old).ok_or(Error::AccountDontExists)?;
+            wallet.allowed_keys[index] = new;
+            self.key_wallets.insert(key_pointer,&wallet);
+
+            self.unindex_key(old,key_pointer);
+            self.index_key(new,key_pointer);
+
+            Self::env().emit_event(KeysUpdated{
+                team: key_pointer,
+                action: KeyAction::REMOVE,
+                account: old,
+                time
+            });
+            Self::env().emit_event(KeysUpdated{
+                team: key_pointer,
+                action: KeyAction::ADD,
+                account: new,
+                time
+            });
+
+            Ok(())
+        }
+
+        /// Whether an approved removal of the account is waiting out the handover delay
+        fn removal_delayed(&self,key_pointer:AccountId,account:AccountId) -> bool{
+            self.delayed_operations.get(key_pointer).unwrap_or_default().iter()
+                .any(|op| op.operation.removed_account() == Some(account))
+        }
+
+        /// Removes the account from the team's members and keys and from the individual's referenced teams
+        fn remove_from_team(&mut self,key_pointer:AccountId,account:AccountId) -> CreateResult<()>{
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            let wallet = self.key_wallets.get(key_pointer).ok_or(Error::ProfileDontExists)?;
+            let mut profile = self.team_applicant_profile.get(key_pointer).ok_or(Error::ProfileDontExists)?;
+            let role = self.member_role(&wallet,account).ok_or(Error::AccountDontExists)?;
+
+            if role == MemberRole::Admin {
+                // Admins are the key admin, the allowed keys and the members with the admin role
+                let mut admins = wallet.allowed_keys.clone();
+                admins.push(wallet.admin);
+                profile.members.iter().for_each(|mem|{
+                    if mem.1 == MemberRole::Admin {
+                        admins.push(mem.0);
+                    }
+                });
+                admins.sort();
+                admins.dedup();
+
+                if admins.len() <= 1 {
+                    return Err(Error::LastAdmin)
+                }
+                if wallet.admin == account {
+                    return Err(Error::AdminHandoverRequired)
+                }
+            }
+
+            profile.remove_member(account);
+            self.team_applicant_profile.insert(key_pointer,&profile);
+
+            if wallet.allowed_keys.contains(&account){
+                self.apply_key_action(key_pointer,account,KeyAction::REMOVE)?;
+            }
+
+            if let Some(mut acc_data) = self.individual_profile.get(account){
+                acc_data.remove_ref_team(key_pointer);
+                self.individual_profile.insert(account,&acc_data);
+            }
+
+            Self::env().emit_event(MemberRemoved{
+                team: key_pointer,
+                member: account,
+                by: caller,
+                time
+            });
+
+            Ok(())
+        }
+
+        /// Stores the invitation, replacing any previous invitation to the same team
+        fn invite(&mut self,team:AccountId,account:AccountId,role:MemberRole,invited_by:AccountId,expires:Timestamp){
+            let mut invitations = self.invitations.get(account).unwrap_or_default();
+            invitations.retain(|invitation| invitation.team != team);
+            invitations.push(Invitation{
+                team,
+                role: role.clone(),
+                invited_by,
+                expires
+            });
+            self.invitations.insert(account,&invitations);
+
+            let mut invited = self.invited.get(team).unwrap_or_default();
+            if !invited.contains(&account) {
+                invited.push(account);
+                self.invited.insert(team,&invited);
+            }
+
+            Self::env().emit_event(MemberInvited{
+                team,
+                account,
+                role,
+                expires
+            });
+        }
+
+        /// Removes and returns the caller's invitation to the team
+        fn take_invitation(&mut self,account:AccountId,team:AccountId) -> CreateResult<Invitation>{
+            let mut invitations = self.invitations.get(account).unwrap_or_default();
+            let index = invitations.iter().position(|invitation| invitation.team == team)
+                .ok_or(Error::InvitationNotFound)?;
+            let invitation = invitations.remove(index);
+
+            if invitations.is_empty(){
+                self.invitations.remove(account);
+            }else{
+                self.invitations.insert(account,&invitations);
+            }
+
+            let mut invited = self.invited.get(team).unwrap_or_default();
+            invited.retain(|invitee| *invitee != account);
+            self.invited.insert(team,&invited);
+            Ok(invitation)
+        }
+
+        /// Removes the state kept per key pointer of a deleted team, so a team registered
+        /// again under the same account starts from scratch
+        fn clear_team(&mut self,key_pointer:AccountId){
+            self.invited.take(key_pointer).unwrap_or_default().into_iter().for_each(|account|{
+                let mut invitations = self.invitations.get(account).unwrap_or_default();
+                invitations.retain(|invitation| invitation.team != key_pointer);
+                if invitations.is_empty(){
+                    self.invitations.remove(account);
+                }else{
+                    self.invitations.insert(account,&invitations);
+                }
+            });
+
+            self.team_roles.remove(key_pointer);
+            self.pending_operations.remove(key_pointer);
+            self.delayed_operations.remove(key_pointer);
+            self.operation_nonce.remove(key_pointer);
+            self.recovery_configs.remove(key_pointer);
+            self.recovery_requests.remove(key_pointer);
+            self.admin_handovers.remove(key_pointer);
+        }
+
+        /// Fails if an escrow of the `owner`'s projects still holds funds
+        fn ensure_escrows_settled(&self,owner:AccountId) -> CreateResult<()>{
+            let held = self.projects.get(owner).unwrap_or_default().iter()
+                .filter_map(|project| self.escrows.get((owner,project.id)))
+                .any(|escrow| escrow.remaining() > 0);
+            if held {
+                return Err(Error::EscrowNotSettled)
+            }
+            Ok(())
+        }
+
+        /// Moves the proposals of a deleted profile out of the live storage following the `policy`
+        /// Settled escrows are removed so they don't attach to new projects reusing the ids.
+        fn erase_proposals(&mut self,id:AccountId,policy:&ErasurePolicy){
+            if let Some(mut projects) = self.projects.take(id){
+                projects.iter().for_each(|project| self.escrows.remove((id,project.id)));
+                if *policy == ErasurePolicy::Tombstone {
+                    projects.iter_mut().for_each(|project| project.tombstone());
+                }
+                // Proposals of an earlier profile under the same account stay archived
+                let mut archived = self.archived_proposals.get(id).unwrap_or_default();
+                archived.append(&mut projects);
+                self.archived_proposals.insert(id,&archived);
+            }
+        }
+        
+      
+    }
+
+
+
+    impl CreateProfile for OrdumState {
+
+        #[ink(message, selector = 0xC0DE0001)]
+        fn create_individual_profile(
+            &mut self,
+            name: String,
+            description: String,
+            categories: Vec<Categories>,
+            chain: Vec<Chains>,
+            links: Vec<String>,
+            role: UserRole
+
+        ) -> CreateResult<()>{
+
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+            // Check if the profile is there
+            if self.individual_profile.get(caller).is_none(){
+
+                let profile = IndividualProfile::new(name.clone(),caller,description,chain,categories,links,role);
+                self.individual_profile.insert(caller,&profile);
+
+                // update all individual account keys
+                self.all_individuals.push((name.clone(),caller));
+
+                Self::env().emit_event(IndividualProfileCreated{
+                    name,
+                    account: caller,
+                    time
+                })
+
+            }else{
+                Err(Error::AccountExists)?
+            }
+
+            Ok(())
+        }
+    
+    
+        #[ink(message, selector = 0xC0DE0002)]
+        fn update_individual_profile(
+            &mut self,
+            description: Option<String>,
+            categories: Option<Vec<Categories>>,
+            chain: Option<Vec<Chains>>,
+            links: Option<Vec<String>>,
+            role: Option<UserRole>
+
+        ) -> CreateResult<()>{
+
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            let mut profile = self.individual_profile.get(caller).ok_or(Error::AccountDontExists)?;
+            let fields = profile.update_profile(description,categories,chain,links,role);
+
+            // Nothing to write if all provided fields are the same
+            if !fields.is_empty() {
+                self.individual_profile.insert(caller,&profile);
+
+                Self::env().emit_event(IndividualProfileUpdated{
+                    account: caller,
+                    fields,
+                    time
+                })
+            }
+
+            Ok(())
+        }
+
+
+        #[ink(message,selector =0xC0DE0003)]
+        fn create_applicant_profile(
+            
+            &mut self, name: String,
+            account: Option<AccountId>,
+            description: String,
+            mission: String,
+            categories: Vec<Categories>,
+            chain: Vec<Chains>,
+            members: Vec<(AccountId, MemberRole)>,
+            links: Vec<String>,
+           
+
+        ) -> CreateResult<()> {
+
+            let applicant = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            // Check if account is provided or else use applicant account
+            let team_account = account.unwrap_or(applicant);
+            // Check if account exists
+            if self.team_applicant_profile.contains(team_account){
+                return Err(Error::AccountExists);
+            }
+
+            // The creator is the first admin, the other members join once they accept the invitation
+            let team_applicant_data = TeamApplicantProfile::new(name,description,mission,team_account,time,categories,chain,vec![(applicant,MemberRole::Admin)],links)
+                .map_err(|_|Error::UnexpectedError)?;
+
+            // Update the Key Mangement, invited admins are granted keys once they accept
+            let wallet_data = KeyManagement {
+                admin: applicant,
+                key_pointer: team_account,
+                allowed_keys: vec![applicant],
+                threshold: 1,
+                max_keys: DEFAULT_MAX_KEYS.min(self.key_ceiling()),
+            };
+
+            // Reserve room for the invited admins
+            let admins = members.iter().filter(|mem| mem.1 == MemberRole::Admin && mem.0 != applicant).count();
+            if admins > wallet_data.remaining_keys() as usize {
+                return Err(Error::MaxKeysExceeded)
+            }
+
+            // Register Keys
+            self.register_wallet(&wallet_data)?;
+
+            members.into_iter().filter(|mem| mem.0 != applicant).for_each(|mem|{
+                self.invite(team_account,mem.0,mem.1,applicant,time.saturating_add(DEFAULT_INVITATION_TTL));
+            });
+
+            if let Some(mut acc_data) = self.individual_profile.get(applicant){
+                acc_data.update_ref_team(team_account,MemberRole::Admin)?;
+                self.individual_profile.insert(applicant,&acc_data);
+            }
+
+            let _applicant_val_bytes = self.team_applicant_profile.insert(wallet_data.key_pointer,&team_applicant_data);
+
+            // update all team account keys
+            self.all_applicant_teams.push((team_applicant_data.clone().name,wallet_data.key_pointer));
+
+            // Emits an event
+            Self::env().emit_event(TeamApplicantCreated{
+                name:team_applicant_data.clone().name,
+                account: team_applicant_data.clone().account_id,
+                time,
+            });
+
+            Ok(())
+        }
+
+
+
+        #[ink(message,selector =0xC0DE0005)]
+        fn update_applicant_profile(
+            &mut self,
+            team: Option<AccountId>,
+            name: Option<String>,
+            description: Option<String>,
+            mission: Option<String>,
+            categories: Option<Vec<Categories>>,
+            chain: Option<Vec<Chains>>,
+            links: Option<Vec<String>>,
+        ) -> CreateResult<()> {
+
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            // Check if the caller is authorized to update the Applicant profile
+            let wallet = self.authorize(caller,team,Capability::EditProfile).ok_or(Error::NotAuthorized)?;
+
+            let mut profile = self.team_applicant_profile.get(wallet.key_pointer)
+                .ok_or(Error::ProfileDontExists)?;
+
+            let fields = profile.update_profile(name,description,mission,categories,chain,links);
+
+            // Nothing to write if all provided fields are the same
+            if fields.is_empty() {
+                return Ok(())
+            }
+            self.team_applicant_profile.insert(wallet.key_pointer,&profile);
+
+            // Keep the team listing in sync with the new name
+            if fields.contains(&ProfileField::Name) {
+                if let Some(team) = self.all_applicant_teams.iter_mut().find(|team| team.1 == wallet.key_pointer){
+                    team.0 = profile.name.clone();
+                }
+            }
+
+            Self::env().emit_event(TeamApplicantUpdated{
+                name: profile.name,
+                fields,
+                time
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0040)]
+        fn create_issuer_profile(
+            &mut self,
+            name: String,
+            account: Option<AccountId>,
+            description: String,
+            chain: Vec<Chains>,
+            categories: Vec<Categories>,
+            grant_levels: Vec<GrantLevel>,
+            requirements: Vec<String>,
+            allowed_keys: Vec<AccountId>,
+        ) -> CreateResult<()> {
+
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            // Check if account is provided or else use caller account
+            let issuer_account = account.unwrap_or(caller);
+            if self.issuer_ids.contains(issuer_account){
+                return Err(Error::AccountExists);
+            }
+
+            // The caller is the admin and always an allowed key
+            let mut keys = vec![caller];
+            allowed_keys.into_iter().for_each(|key|{
+                if !keys.contains(&key){
+                    keys.push(key);
+                }
+            });
+
+            let max_keys = DEFAULT_MAX_KEYS.min(self.key_ceiling());
+            if keys.len() > max_keys as usize {
+                return Err(Error::MaxKeysExceeded)
+            }
+
+            let mut all_issuers = self.all_issuers.get().unwrap_or_default();
+            let id = (all_issuers.len() as u16).checked_add(1).ok_or(Error::UnexpectedError)?;
+            let issuer = IssuerProfile {
+                id,
+                name: name.clone(),
+                description,
+                chain,
+                categories,
+                grant_levels,
+                requirements,
+                registered_time: time,
+                keys: KeyManagement {
+                    admin: caller,
+                    key_pointer: issuer_account,
+                    allowed_keys: keys,
+                    threshold: 1,
+                    max_keys,
+                }
+            };
+
+            self.issuer_profile.insert(id,&issuer);
+            self.issuer_ids.insert(issuer_account,&id);
+            all_issuers.push((name.clone(),id));
+            self.all_issuers.set(&all_issuers);
+
+            Self::env().emit_event(IssuerProfileCreated{
+                name,
+                id,
+                account: issuer_account,
+                time
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE005A)]
+        fn update_issuer_keys(&mut self, issuer_id: u16, account: AccountId, action: KeyAction) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let mut issuer = self.issuer_profile.get(issuer_id).ok_or(Error::ProfileDontExists)?;
+            if issuer.keys.admin != caller {
+                return Err(Error::NotAuthorized)
+            }
+
+            // The admin is always one of the allowed keys
+            match action {
+                KeyAction::REMOVE if account == issuer.keys.admin => return Err(Error::NotAuthorized),
+                KeyAction::ChangeAdmin if !issuer.is_allowed(&account) => return Err(Error::AccountDontExists),
+                _ => {}
+            }
+            issuer.keys.update_keys_inner(account,action.clone())?;
+            self.issuer_profile.insert(issuer_id,&issuer);
+
+            Self::env().emit_event(KeysUpdated{
+                team: issuer.keys.key_pointer,
+                action,
+                account,
+                time: Self::env().block_timestamp()
+            });
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0004)]
+        fn update_keys(&mut self, team: Option<AccountId>, account: AccountId, action: KeyAction) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            // check if the account is registered as admin of the specified team
+            // or else of the only team it administers
+            let key_pointer = match team {
+                Some(team_inner) => team_inner,
+                None => {
+                    let mut administered = self.teams_of(caller).into_iter().filter(|key_pointer|{
+                        self.authorize(caller,Some(*key_pointer),Capability::ManageKeys).is_some()
+                    });
+
+                    let key_pointer = administered.next().ok_or(Error::NotAuthorized)?;
+                    if administered.next().is_some() {
+                        return Err(Error::TeamNotSpecified)
+                    }
+                    key_pointer
+                }
+            };
+
+            let acc_manage = self.authorize(caller,Some(key_pointer),Capability::ManageKeys).ok_or(Error::NotAuthorized)?;
+
+            // Removing keys and changing the admin are queued for teams requiring several approvals
+            if acc_manage.threshold > 1 {
+                match action {
+                    KeyAction::REMOVE => {
+                        self.queue_operation(&acc_manage,caller,TeamOperation::RemoveKey(account));
+                        return Ok(())
+                    },
+                    KeyAction::ChangeAdmin => {
+                        self.queue_operation(&acc_manage,caller,TeamOperation::ChangeAdmin(account));
+                        return Ok(())
+                    },
+                    KeyAction::ADD => {}
+                }
+            }
+
+            match action {
+                // The new admin takes over only after accepting the handover
+                KeyAction::ChangeAdmin => self.start_handover(key_pointer,caller,account),
+                KeyAction::REMOVE => self.execute_operation(key_pointer,caller,TeamOperation::RemoveKey(account)),
+                KeyAction::ADD => self.apply_key_action(key_pointer,account,action)
+            }
+        }
+
+
+        #[ink(message, selector = 0xC0DE0020)]
+        fn delete_individual_profile(&mut self,policy: ErasurePolicy) -> CreateResult<()> {
+
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            let profile = self.individual_profile.get(caller).ok_or(Error::AccountDontExists)?;
+            // Team keys are given up through `update_keys` or `leave_team` before deleting the profile
+            if !self.key_index.get(caller).unwrap_or_default().is_empty() {
+                return Err(Error::TeamKeysHeld)
+            }
+
+            // Unlink the individual from the teams' member list
+            profile.ref_team.iter().for_each(|team|{
+                if let Some(mut team_profile) = self.team_applicant_profile.get(team.0){
+                    team_profile.remove_member(caller);
+                    self.team_applicant_profile.insert(team.0,&team_profile);
+                }
+            });
+
+            // A team created without its own account stores its proposals under the creator's account
+            let owns_proposals = !self.key_wallets.contains(caller);
+            if owns_proposals {
+                self.ensure_escrows_settled(caller)?;
+            }
+
+            self.individual_profile.remove(caller);
+            self.all_individuals.retain(|individual| individual.1 != caller);
+            self.db_auth.remove(caller);
+            if owns_proposals {
+                self.erase_proposals(caller,&policy);
+            }
+
+            Self::env().emit_event(IndividualProfileDeleted{
+                account: caller,
+                policy,
+                time
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0021)]
+        fn delete_applicant_profile(&mut self,team: Option<AccountId>,policy: ErasurePolicy) -> CreateResult<()> {
+
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            // Only the admin can delete the team
+            let wallet = self.authorize(caller,team,Capability::ManageKeys).ok_or(Error::NotAuthorized)?;
+            self.ensure_escrows_settled(wallet.key_pointer)?;
+            self.unregister_wallet(&wallet);
+            self.clear_team(wallet.key_pointer);
+
+            let profile = self.team_applicant_profile.get(wallet.key_pointer)
+                .ok_or(Error::ProfileDontExists)?;
+
+            // Unlink the team from the members' individual profiles
+            profile.members.iter().for_each(|mem|{
+                if let Some(mut acc_data) = self.individual_profile.get(mem.0){
+                    acc_data.remove_ref_team(wallet.key_pointer);
+                    self.individual_profile.insert(mem.0,&acc_data);
+                }
+            });
+
+            self.team_applicant_profile.remove(wallet.key_pointer);
+            self.all_applicant_teams.retain(|team| team.1 != wallet.key_pointer);
+            self.db_auth.remove(wallet.key_pointer);
+            self.erase_proposals(wallet.key_pointer,&policy);
+
+            Self::env().emit_event(TeamApplicantDeleted{
+                name: profile.name,
+                account: wallet.key_pointer,
+                policy,
+                time
+            });
+
+            Ok(())
+        }
+       
+    }
+
+
+
+    impl MultiKey for OrdumState {
+
+        #[ink(message, selector = 0xC0DE0022)]
+        fn set_threshold(&mut self,team: Option<AccountId>,threshold: u8) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let wallet = self.authorize(caller,team,Capability::ApproveOperations).ok_or(Error::NotAuthorized)?;
+
+            // Changing the threshold is itself guarded by the current threshold
+            if wallet.threshold > 1 {
+                self.queue_operation(&wallet,caller,TeamOperation::SetThreshold(threshold));
+                Ok(())
+            }else if self.authorize(caller,Some(wallet.key_pointer),Capability::ManageKeys).is_none() {
+                Err(Error::NotAuthorized)
+            }else{
+                self.execute_operation(wallet.key_pointer,caller,TeamOperation::SetThreshold(threshold))
+            }
+        }
+
+
+        #[ink(message, selector = 0xC0DE0023)]
+        fn approve_operation(&mut self,team: Option<AccountId>,operation_id: u32) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+            let wallet = self.authorize(caller,team,Capability::ApproveOperations).ok_or(Error::NotAuthorized)?;
+
+            let mut operations = self.pending_operations.get(wallet.key_pointer).unwrap_or_default();
+            let index = operations.iter().position(|op| op.id == operation_id)
+                .ok_or(Error::OperationNotFound)?;
+
+            if operations[index].approvals.contains(&caller){
+                return Err(Error::AlreadyApproved)
+            }
+            operations[index].approvals.push(caller);
+
+            let approvals = wallet.approvals_count(&operations[index].approvals);
+            Self::env().emit_event(OperationApproved{
+                team: wallet.key_pointer,
+                id: operation_id,
+                approver: caller,
+                approvals,
+                threshold: wallet.threshold
+            });
+
+            if approvals >= wallet.threshold {
+                let pending = operations.remove(index);
+                self.pending_operations.insert(wallet.key_pointer,&operations);
+
+                self.execute_operation(wallet.key_pointer,pending.proposer,pending.operation)?;
+
+                Self::env().emit_event(OperationExecuted{
+                    team: wallet.key_pointer,
+                    id: operation_id,
+                    time
+                });
+            }else{
+                self.pending_operations.insert(wallet.key_pointer,&operations);
+            }
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0024)]
+        fn cancel_operation(&mut self,team: Option<AccountId>,operation_id: u32) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let wallet = self.authorize(caller,team,Capability::ApproveOperations).ok_or(Error::NotAuthorized)?;
+
+            let mut operations = self.pending_operations.get(wallet.key_pointer).unwrap_or_default();
+            let index = operations.iter().position(|op| op.id == operation_id)
+                .ok_or(Error::OperationNotFound)?;
+
+            let is_admin = self.authorize(caller,Some(wallet.key_pointer),Capability::ManageKeys).is_some();
+            if operations[index].proposer != caller && !is_admin {
+                return Err(Error::NotAuthorized)
+            }
+            operations.remove(index);
+            self.pending_operations.insert(wallet.key_pointer,&operations);
+
+            Self::env().emit_event(OperationCancelled{
+                team: wallet.key_pointer,
+                id: operation_id,
+                by: caller
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0039)]
+        fn set_max_keys(&mut self,team: Option<AccountId>,max_keys: u8) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let mut wallet = self.authorize(caller,team,Capability::ManageKeys).ok_or(Error::NotAuthorized)?;
+
+            wallet.update_max_keys(max_keys,self.key_ceiling())?;
+            self.key_wallets.insert(wallet.key_pointer,&wallet);
+
+            Self::env().emit_event(MaxKeysUpdated{
+                team: wallet.key_pointer,
+                max_keys
+            });
+
+            Ok(())
+        }
+
+    }
+
+
+
+    impl TeamPermissions for OrdumState {
+
+        #[ink(message, selector = 0xC0DE0025)]
+        fn define_role(&mut self,team: Option<AccountId>,name: String,capabilities: Vec<Capability>) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let wallet = self.authorize(caller,team,Capability::ManageMembers).ok_or(Error::NotAuthorized)?;
+
+            if capabilities.iter().any(|capability| matches!(capability,Capability::ManageKeys | Capability::ApproveOperations)) {
+                return Err(Error::InvalidRole)
+            }
+
+            let mut roles = self.team_roles.get(wallet.key_pointer).unwrap_or_default();
+            let id = match roles.last() {
+                Some(team_role) => team_role.id.checked_add(1).ok_or(Error::MaxRolesExceeded)?,
+                None => 1
+            };
+            roles.push(TeamRole{
+                id,
+                name: name.clone(),
+                capabilities
+            });
+            self.team_roles.insert(wallet.key_pointer,&roles);
+
+            Self::env().emit_event(RoleDefined{
+                team: wallet.key_pointer,
+                id,
+                name
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0026)]
+        fn assign_role(&mut self,team: Option<AccountId>,member: AccountId,role: MemberRole) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let wallet = self.authorize(caller,team,Capability::ManageMembers).ok_or(Error::NotAuthorized)?;
+
+            // Admins are the allowed keys, they are added through `update_keys`
+            if role == MemberRole::Admin {
+                return Err(Error::InvalidRole)
+            }
+            if let MemberRole::Custom(id) = role {
+                if !self.team_roles.get(wallet.key_pointer).unwrap_or_default().iter().any(|team_role| team_role.id == id){
+                    return Err(Error::RoleNotFound)
+                }
+            }
+
+            let mut profile = self.team_applicant_profile.get(wallet.key_pointer).ok_or(Error::ProfileDontExists)?;
+            profile.update_member_role(member,role.clone())?;
+            self.team_applicant_profile.insert(wallet.key_pointer,&profile);
+
+            if let Some(mut acc_data) = self.individual_profile.get(member){
+                acc_data.update_ref_team_role(wallet.key_pointer,role.clone());
+                self.individual_profile.insert(member,&acc_data);
+            }
+
+            Self::env().emit_event(RoleAssigned{
+                team: wallet.key_pointer,
+                member,
+                role
+            });
+
+            Ok(())
+        }
+
+    }
+
+
+
+    impl Membership for OrdumState {
+
+        #[ink(message, selector = 0xC0DE0027)]
+        fn invite_member(&mut self,team: Option<AccountId>,account: AccountId,role: MemberRole,expires_in: Option<Timestamp>) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+            let wallet = self.authorize(caller,team,Capability::ManageMembers).ok_or(Error::NotAuthorized)?;
+
+            // Only admins can invite admins
+            if role == MemberRole::Admin && self.member_role(&wallet,caller) != Some(MemberRole::Admin) {
+                return Err(Error::NotAuthorized)
+            }
+            if let MemberRole::Custom(id) = role {
+                if !self.team_roles.get(wallet.key_pointer).unwrap_or_default().iter().any(|team_role| team_role.id == id){
+                    return Err(Error::RoleNotFound)
+                }
+            }
+
+            let profile = self.team_applicant_profile.get(wallet.key_pointer).ok_or(Error::ProfileDontExists)?;
+            if profile.member_role(account).is_some(){
+                return Err(Error::AccountExists)
+            }
+
+            let expires = time.saturating_add(expires_in.unwrap_or(DEFAULT_INVITATION_TTL));
+            self.invite(wallet.key_pointer,account,role,caller,expires);
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0028)]
+        fn accept_invitation(&mut self,team: AccountId) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            let invitation = self.take_invitation(caller,team)?;
+            // Expired invitations are dropped, returning an error would keep them around
+            if invitation.expires < time {
+                Self::env().emit_event(InvitationLapsed{
+                    team,
+                    account: caller
+                });
+                return Ok(())
+            }
+
+            let mut profile = self.team_applicant_profile.get(team).ok_or(Error::ProfileDontExists)?;
+            profile.add_member(caller,invitation.role.clone())?;
+            self.team_applicant_profile.insert(team,&profile);
+
+            // Hook the team up in the individual profile
+            if let Some(mut acc_data) = self.individual_profile.get(caller){
+                acc_data.update_ref_team(team,invitation.role.clone())?;
+                self.individual_profile.insert(caller,&acc_data);
+            }
+
+            // Invited admins are granted a key
+            if invitation.role == MemberRole::Admin {
+                let wallet = self.key_wallets.get(team).ok_or(Error::UnexpectedError)?;
+                if !wallet.allowed_keys.contains(&caller){
+                    self.apply_key_action(team,caller,KeyAction::ADD)?;
+                }
+            }
+
+            Self::env().emit_event(UpdatedTeamMembership{
+                team_name: profile.name,
+                team_id: team,
+                individual_id: caller,
+                time
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0029)]
+        fn decline_invitation(&mut self,team: AccountId) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            self.take_invitation(caller,team)?;
+
+            Self::env().emit_event(InvitationDeclined{
+                team,
+                account: caller
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0030)]
+        fn remove_member(&mut self,team: Option<AccountId>,member: AccountId) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let wallet = self.authorize(caller,team,Capability::ManageMembers).ok_or(Error::NotAuthorized)?;
+
+            // Only admins can remove admins
+            if self.member_role(&wallet,member) == Some(MemberRole::Admin) && self.member_role(&wallet,caller) != Some(MemberRole::Admin) {
+                return Err(Error::NotAuthorized)
+            }
+
+            // Removing a key is queued for teams requiring several approvals
+            if wallet.threshold > 1 && wallet.allowed_keys.contains(&member) {
+                self.queue_operation(&wallet,caller,TeamOperation::RemoveMember(member));
+                return Ok(())
+            }
+
+            self.execute_operation(wallet.key_pointer,caller,TeamOperation::RemoveMember(member))
+        }
+
+
+        #[ink(message, selector = 0xC0DE0031)]
+        fn leave_team(&mut self,team: AccountId) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            self.remove_from_team(team,caller)
+        }
+
     }
 
 
 
-    impl CreateProfile for OrdumState {
+    impl KeyRecovery for OrdumState {
 
-        #[ink(message, selector = 0xC0DE0001)]
-        fn create_individual_profile(
-            &mut self,
-            name: String,
-            description: String,
-            categories: Vec<Categories>,
-            chain: Vec<Chains>,
-            links: Vec<String>,
-            role: UserRole
+        #[ink(message, selector = 0xC0DE0032)]
+        fn set_recovery_config(&mut self,team: Option<AccountId>,config: RecoveryConfig) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let wallet = self.authorize(caller,team,Capability::ManageKeys).ok_or(Error::NotAuthorized)?;
+            config.validate()?;
 
-        ) -> CreateResult<()>{
+            if wallet.threshold > 1 {
+                self.queue_operation(&wallet,caller,TeamOperation::SetRecoveryConfig(config));
+                Ok(())
+            }else{
+                self.execute_operation(wallet.key_pointer,caller,TeamOperation::SetRecoveryConfig(config))
+            }
+        }
 
+
+        #[ink(message, selector = 0xC0DE0033)]
+        fn accept_admin(&mut self,team: AccountId) -> CreateResult<()> {
             let caller = Self::env().caller();
             let time = Self::env().block_timestamp();
-            // Check if the profile is there
-            if self.individual_profile.get(caller).is_none(){
 
-                let profile = IndividualProfile::new(name.clone(),caller,description,chain,categories,links,role);
-                self.individual_profile.insert(caller,&profile);
+            let handover = self.admin_handovers.get(team).ok_or(Error::HandoverNotFound)?;
+            if handover.new_admin != caller {
+                return Err(Error::NotAuthorized)
+            }
+            if time < handover.executable_at {
+                return Err(Error::TimeLocked)
+            }
 
-                // update all individual account keys
-                self.all_individuals.push((name.clone(),caller));
+            self.change_admin(team,caller)
+        }
+
+
+        #[ink(message, selector = 0xC0DE0034)]
+        fn veto_admin_handover(&mut self,team: Option<AccountId>) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+            let wallet = self.wallet_of(caller,team).ok_or(Error::NotAuthorized)?;
+
+            let handover = self.admin_handovers.get(wallet.key_pointer).ok_or(Error::HandoverNotFound)?;
+            // A key on its way out can't hold the team hostage
+            if handover.proposed_by == caller || self.removal_delayed(wallet.key_pointer,caller) {
+                return Err(Error::NotAuthorized)
+            }
+            // The veto window closes once the delay has passed
+            if time >= handover.executable_at {
+                return Err(Error::TimeLocked)
+            }
+            self.admin_handovers.remove(wallet.key_pointer);
+
+            Self::env().emit_event(AdminHandoverVetoed{
+                team: wallet.key_pointer,
+                by: caller
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0035)]
+        fn initiate_recovery(&mut self,team: AccountId,new_admin: AccountId) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            let config = self.recovery_configs.get(team).unwrap_or_default();
+            if !config.guardians.contains(&caller) {
+                return Err(Error::NotAuthorized)
+            }
+            // A pending request can't be replaced, that would reset its approvals
+            if self.active_recovery(team,time).is_some() {
+                return Err(Error::RecoveryPending)
+            }
+
+            let executable_at = time.saturating_add(config.recovery_delay);
+            self.recovery_requests.insert(team,&RecoveryRequest{
+                new_admin,
+                approvals: vec![caller],
+                executable_at,
+                expires: executable_at.saturating_add(RECOVERY_REQUEST_TTL)
+            });
+
+            Self::env().emit_event(RecoveryInitiated{
+                team,
+                new_admin,
+                guardian: caller,
+                executable_at
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0036)]
+        fn support_recovery(&mut self,team: AccountId) -> CreateResult<()> {
+            let caller = Self::env().caller();
+
+            let config = self.recovery_configs.get(team).unwrap_or_default();
+            if !config.guardians.contains(&caller) {
+                return Err(Error::NotAuthorized)
+            }
+
+            let mut request = self.active_recovery(team,Self::env().block_timestamp()).ok_or(Error::RecoveryNotFound)?;
+            if request.approvals.contains(&caller) {
+                return Err(Error::AlreadyApproved)
+            }
+            request.approvals.push(caller);
+            self.recovery_requests.insert(team,&request);
+
+            Self::env().emit_event(RecoverySupported{
+                team,
+                guardian: caller,
+                approvals: request.approvals.len() as u8
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0037)]
+        fn execute_recovery(&mut self,team: AccountId) -> CreateResult<()> {
+            let time = Self::env().block_timestamp();
+
+            let config = self.recovery_configs.get(team).unwrap_or_default();
+            let request = self.active_recovery(team,time).ok_or(Error::RecoveryNotFound)?;
+
+            // Only approvals of current guardians count
+            let approvals = request.approvals.iter().filter(|guardian| config.guardians.contains(guardian)).count();
+            if config.threshold == 0 || approvals < config.threshold as usize {
+                return Err(Error::NotAuthorized)
+            }
+            if time < request.executable_at {
+                return Err(Error::TimeLocked)
+            }
+
+            self.change_admin(team,request.new_admin)
+        }
 
-                Self::env().emit_event(IndividualProfileCreated{
-                    name,
-                    account: caller,
-                    time
-                })
 
+        #[ink(message, selector = 0xC0DE0038)]
+        fn cancel_recovery(&mut self,team: AccountId) -> CreateResult<()> {
+            let caller = Self::env().caller();
+
+            let mut request = self.recovery_requests.get(team).ok_or(Error::RecoveryNotFound)?;
+            if !request.approvals.contains(&caller) {
+                return Err(Error::NotAuthorized)
+            }
+            request.approvals.retain(|guardian| *guardian != caller);
+
+            if request.approvals.is_empty() {
+                self.recovery_requests.remove(team);
+                Self::env().emit_event(RecoveryCancelled{
+                    team
+                });
             }else{
-                Err(Error::AccountExists)?
+                self.recovery_requests.insert(team,&request);
             }
 
             Ok(())
         }
-    
-    
-        #[ink(message, selector = 0xC0DE0002)]
-        fn update_individual_profile(
+
+
+        #[ink(message, selector = 0xC0DE0058)]
+        fn execute_delayed_operation(&mut self,team: AccountId,operation_id: u32) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+            self.authorize(caller,Some(team),Capability::ApproveOperations).ok_or(Error::NotAuthorized)?;
+
+            let mut delayed = self.delayed_operations.get(team).unwrap_or_default();
+            let index = delayed.iter().position(|op| op.id == operation_id)
+                .ok_or(Error::OperationNotFound)?;
+            if time < delayed[index].executable_at {
+                return Err(Error::TimeLocked)
+            }
+            let operation = delayed.remove(index);
+            self.delayed_operations.insert(team,&delayed);
+
+            self.apply_operation(team,operation.proposer,operation.operation)?;
+
+            Self::env().emit_event(OperationExecuted{
+                team,
+                id: operation_id,
+                time
+            });
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0059)]
+        fn veto_delayed_operation(&mut self,team: AccountId,operation_id: u32) -> CreateResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            let mut delayed = self.delayed_operations.get(team).unwrap_or_default();
+            let index = delayed.iter().position(|op| op.id == operation_id)
+                .ok_or(Error::OperationNotFound)?;
+
+            let is_key = self.authorize(caller,Some(team),Capability::ApproveOperations).is_some();
+            let is_guardian = self.recovery_configs.get(team).unwrap_or_default().guardians.contains(&caller);
+            if (!is_key || delayed[index].proposer == caller) && !is_guardian {
+                return Err(Error::NotAuthorized)
+            }
+            // The removed account can't veto its own removal
+            if delayed[index].operation.removed_account() == Some(caller) {
+                return Err(Error::NotAuthorized)
+            }
+            // The veto window closes once the delay has passed
+            if time >= delayed[index].executable_at {
+                return Err(Error::TimeLocked)
+            }
+            delayed.remove(index);
+            self.delayed_operations.insert(team,&delayed);
+
+            Self::env().emit_event(OperationCancelled{
+                team,
+                id: operation_id,
+                by: caller
+            });
+            Ok(())
+        }
+
+    }
+
+
+
+    impl GrantPrograms for OrdumState {
+
+        #[ink(message, selector = 0xC0DE0041)]
+        fn create_program(
             &mut self,
-            _description: Option<String>,
-            _categories: Option<Vec<Categories>>,
-            _chain: Option<Vec<Chains>>,
-            _links: Option<Vec<String>>,
-            _role: Option<UserRole>
+            issuer_id: u16,
+            name: String,
+            open: Timestamp,
+            close: Timestamp,
+            budget: Balance,
+            categories: Vec<Categories>,
+            chains: Vec<Chains>,
+            max_ask: Balance,
+            template: String,
+        ) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            self.authorize_issuer(caller,issuer_id)?;
 
-        ) -> CreateResult<()>{
+            if open >= close || close <= Self::env().block_timestamp() {
+                return Err(Error::InvalidSchedule)
+            }
+
+            let id = self.program_count.get().unwrap_or_default().checked_add(1).ok_or(Error::UnexpectedError)?;
+            let program = GrantProgram {
+                id,
+                issuer_id,
+                name: name.clone(),
+                open,
+                close,
+                budget,
+                categories,
+                chains,
+                max_ask,
+                template,
+                quota: None,
+                closed: false,
+                proposals: vec![]
+            };
+
+            let mut programs = self.issuer_programs.get(issuer_id).unwrap_or_default();
+            programs.push(id);
+
+            self.grant_programs.insert(id,&program);
+            self.issuer_programs.insert(issuer_id,&programs);
+            self.program_count.set(&id);
+
+            Self::env().emit_event(GrantProgramCreated{
+                issuer_id,
+                id,
+                name,
+                open,
+                close
+            });
 
             Ok(())
         }
 
 
-        #[ink(message,selector =0xC0DE0003)]
-        fn create_applicant_profile(
-            
-            &mut self, name: String,
-            account: Option<AccountId>,
-            description: String,
-            mission: String,
+        #[ink(message, selector = 0xC0DE0042)]
+        fn update_program(
+            &mut self,
+            program_id: u32,
+            name: String,
+            open: Timestamp,
+            close: Timestamp,
+            budget: Balance,
             categories: Vec<Categories>,
-            chain: Vec<Chains>,
-            members: Vec<(AccountId, MemberRole)>,
-            links: Vec<String>,
-           
+            chains: Vec<Chains>,
+            max_ask: Balance,
+            template: String,
+        ) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let mut program = self.grant_programs.get(program_id).ok_or(Error::ProgramNotFound)?;
+            self.authorize_issuer(caller,program.issuer_id)?;
 
-        ) -> CreateResult<()> {
+            if program.closed {
+                return Err(Error::ProgramClosed)
+            }
+            if open >= close || close <= Self::env().block_timestamp() {
+                return Err(Error::InvalidSchedule)
+            }
 
-            let applicant = Self::env().caller();
+            program.name = name;
+            program.open = open;
+            program.close = close;
+            program.budget = budget;
+            program.categories = categories;
+            program.chains = chains;
+            program.max_ask = max_ask;
+            program.template = template;
+            self.grant_programs.insert(program_id,&program);
+
+            Self::env().emit_event(GrantProgramUpdated{
+                issuer_id: program.issuer_id,
+                id: program_id
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0043)]
+        fn close_program(&mut self,program_id: u32) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let mut program = self.grant_programs.get(program_id).ok_or(Error::ProgramNotFound)?;
+            self.authorize_issuer(caller,program.issuer_id)?;
+
+            if program.closed {
+                return Err(Error::ProgramClosed)
+            }
+            program.closed = true;
+            self.grant_programs.insert(program_id,&program);
+
+            Self::env().emit_event(GrantProgramClosed{
+                issuer_id: program.issuer_id,
+                id: program_id,
+                time: Self::env().block_timestamp()
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0051)]
+        fn set_program_quota(&mut self,program_id: u32,quota: u32) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let mut program = self.grant_programs.get(program_id).ok_or(Error::ProgramNotFound)?;
+            self.authorize_issuer(caller,program.issuer_id)?;
+
+            // The attached projects have to fit in the new quota
+            let over_quota = program.proposals.iter().any(|(owner,proposal_id)|{
+                self.projects.get(owner).unwrap_or_default().iter()
+                    .any(|project| project.id == *proposal_id && project.total_mem > quota)
+            });
+            if over_quota {
+                return Err(Error::QuotaBelowUsage)
+            }
+
+            program.quota = Some(quota);
+            self.grant_programs.insert(program_id,&program);
+
+            Self::env().emit_event(StorageQuotaUpdated{
+                program: Some(program_id),
+                quota
+            });
+            Ok(())
+        }
+
+
+        #[ink(message, selector = 0xC0DE0044)]
+        fn attach_proposal(&mut self,team: Option<AccountId>,proposal_id: u8,program_id: u32,ask: Balance) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+
+            if let Some(wallet) = self.authorize(caller,team,Capability::SubmitProposal){
+
+                // Teams requiring several approvals queue the submission
+                if wallet.threshold > 1 {
+                    self.queue_operation(&wallet,caller,TeamOperation::AttachProposal{ project: proposal_id, program: program_id, ask });
+                }else{
+                    self.attach(wallet.key_pointer,proposal_id,program_id,ask)?
+                }
+
+            }else if team.is_some() || !self.individual_profile.contains(caller){
+                Err(Error::NotAuthorized)?
+            }else{
+                self.attach(caller,proposal_id,program_id,ask)?
+            }
+
+            Ok(())
+        }
+
+    }
+
+
+
+    impl OffchainApply for OrdumState {
+
+        #[ink(message,selector = 0xC0DE0006)]
+        fn apply_grant(&mut self,team: Option<AccountId>,issuer_id: u16,proposal_id: u8,file: String) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let time = Self::env().block_timestamp();
+
+            // The applicant is the team or else the caller's individual profile
+            let (applicant,team_name) = if let Some(wallet) = self.authorize(caller,team,Capability::SubmitProposal){
+                let team_profile = self.team_applicant_profile.get(wallet.key_pointer).ok_or(Error::ProfileDontExists)?;
+                (wallet.key_pointer,team_profile.name)
+            }else if team.is_some(){
+                Err(Error::NotAuthorized)?
+            }else{
+                let individual = self.individual_profile.get(caller).ok_or(Error::NotAuthorized)?;
+                (caller,individual.name)
+            };
+
+            let issuer = self.issuer_profile.get(issuer_id).ok_or(Error::ProfileDontExists)?;
+            let mut projects = self.projects.get(applicant).ok_or(Error::ProposalNotFound)?;
+            let project = projects.iter_mut().find(|project| project.id == proposal_id).ok_or(Error::ProposalNotFound)?;
+
+            // A proposal attached to a program can only go to the program's issuer
+            if let Some(program_id) = project.program {
+                let program = self.grant_programs.get(program_id).ok_or(Error::ProgramNotFound)?;
+                if program.issuer_id != issuer_id {
+                    return Err(Error::NotEligible)
+                }
+            }
+
+            // Only drafts and rejected proposals can start a new application
+            if !matches!(project.status,Status::Draft | Status::Rejected) {
+                return Err(Error::InvalidTransition)
+            }
+            // Funds left by another issuer have to be reclaimed before applying elsewhere
+            if self.escrows.get((applicant,proposal_id)).map_or(false,|escrow| escrow.issuer_id != issuer_id && escrow.remaining() > 0) {
+                return Err(Error::EscrowNotSettled)
+            }
+
+            let id = self.application_count.get(issuer_id).unwrap_or_default().checked_add(1).ok_or(Error::UnexpectedError)?;
+            let application = Application {
+                id,
+                team_name,
+                issuer_id,
+                issuer_name: issuer.name,
+                applicant,
+                proposal: proposal_id,
+                program: project.program,
+                file,
+                status: project.status.clone(),
+                feedback: None,
+                submitted: time,
+                updated: time,
+            };
+            project.application = Some((issuer_id,id));
+            self.projects.insert(applicant,&projects);
+
+            let mut applications = self.applicant_applications.get(applicant).unwrap_or_default();
+            applications.push((issuer_id,id));
+            self.queue_applications.insert((issuer_id,id),&application);
+            self.application_count.insert(issuer_id,&id);
+            self.applicant_applications.insert(applicant,&applications);
+
+            self.change_status(applicant,proposal_id,Status::Submitted,Actor::Applicant)?;
+
+            Self::env().emit_event(ApplicationSubmitted{
+                issuer_id,
+                applicant,
+                id,
+                proposal: proposal_id,
+                time
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message,selector = 0xC0DE0007)]
+        fn update_application(&mut self,issuer_id: u16,application_id: u32,file: String) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
             let time = Self::env().block_timestamp();
+            let mut application = self.queue_applications.get((issuer_id,application_id)).ok_or(Error::ApplicationNotFound)?;
+
+            if !self.is_applicant(caller,application.applicant,Capability::SubmitProposal) {
+                return Err(Error::NotAuthorized)
+            }
+
+            if !matches!(application.status,Status::Submitted | Status::ChangesRequested) {
+                return Err(Error::InvalidTransition)
+            }
+            application.file = file.clone();
+            application.updated = time;
+            self.queue_applications.insert((issuer_id,application_id),&application);
+
+            // Requested changes are submitted again
+            if application.status == Status::ChangesRequested {
+                self.change_status(application.applicant,application.proposal,Status::Submitted,Actor::Applicant)?;
+            }
+
+            Self::env().emit_event(ApplicationUpdated{
+                issuer_id,
+                id: application_id,
+                file
+            });
+
+            Ok(())
+        }
+
+
+        #[ink(message,selector = 0xC0DE0008)]
+        fn review_application(&mut self,issuer_id: u16,application_id: u32,status: Status,feedback: Option<String>) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            self.authorize_issuer(caller,issuer_id)?;
+
+            let mut application = self.queue_applications.get((issuer_id,application_id)).ok_or(Error::ApplicationNotFound)?;
+            // Earlier applications of the proposal are settled
+            let active = self.projects.get(application.applicant).unwrap_or_default().iter()
+                .any(|project| project.id == application.proposal && project.application == Some((issuer_id,application_id)));
+            if !active {
+                return Err(Error::InvalidTransition)
+            }
+
+            if feedback.is_some() {
+                application.feedback = feedback;
+                self.queue_applications.insert((issuer_id,application_id),&application);
+            }
+
+            self.change_status(application.applicant,application.proposal,status.clone(),Actor::Issuer)?;
+            self.record_decision((issuer_id,application_id),status);
+            Ok(())
+        }
+
+
+        #[ink(message,selector = 0xC0DE0045)]
+        fn abandon_proposal(&mut self,team: Option<AccountId>,proposal_id: u8) -> ApplicationResult<()> {
+            let owner = self.applicant_of(Self::env().caller(),team,Capability::SubmitProposal)?;
+            self.change_status(owner,proposal_id,Status::Abandoned,Actor::Applicant)
+        }
+
+    }
+
+
+
+    impl GrantEscrow for OrdumState {
+
+        #[ink(message,payable,selector = 0xC0DE0056)]
+        fn deposit_escrow(&mut self,owner: AccountId,proposal_id: u8) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let amount = Self::env().transferred_value();
+            if amount == 0 {
+                return Err(Error::InvalidAmount)
+            }
+
+            let project = self.projects.get(owner).unwrap_or_default().into_iter()
+                .find(|project| project.id == proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            let (issuer_id,_) = project.application.ok_or(Error::ApplicationNotFound)?;
+            self.authorize_issuer(caller,issuer_id)?;
+            if !matches!(project.status,Status::Approved | Status::Funded) {
+                return Err(Error::NotApproved)
+            }
+
+            let mut escrow = self.escrows.get((owner,proposal_id)).unwrap_or(Escrow{
+                issuer_id,
+                deposited: 0,
+                released: 0,
+                reclaimed: 0
+            });
+            // The escrow stays with the issuer which funded it
+            if escrow.issuer_id != issuer_id {
+                return Err(Error::NotAuthorized)
+            }
+            escrow.deposited = escrow.deposited.saturating_add(amount);
+            self.escrows.insert((owner,proposal_id),&escrow);
+
+            if project.status == Status::Approved {
+                self.change_status(owner,proposal_id,Status::Funded,Actor::Issuer)?;
+            }
+
+            Self::env().emit_event(EscrowDeposited{
+                owner,
+                project: proposal_id,
+                issuer_id,
+                depositor: caller,
+                amount
+            });
+            Ok(())
+        }
+
 
-            // Check if account is provided or else use applicant account
-            if let Some(account_inner) = account {
-                // Check using wallet key pointer ?????????
-                // Check if account exists
-                if self.team_applicant_profile.contains(account_inner){
-                    return Err(Error::AccountExists);
-                }
+        #[ink(message,selector = 0xC0DE0057)]
+        fn reclaim_escrow(&mut self,owner: AccountId,proposal_id: u8) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let mut escrow = self.escrows.get((owner,proposal_id)).ok_or(Error::EscrowNotFound)?;
+            self.authorize_issuer(caller,escrow.issuer_id)?;
+
+            let cancelled = match self.projects.get(owner).unwrap_or_default().into_iter().find(|project| project.id == proposal_id) {
+                Some(project) => matches!(project.status,Status::Abandoned | Status::Rejected | Status::Completed),
+                // The owner's profile and proposals were deleted
+                None => true
+            };
+            if !cancelled {
+                return Err(Error::NotCancelled)
+            }
 
-                let team_applicant_data = TeamApplicantProfile::new(name,description,mission,account_inner,time,categories,chain,members.clone(),links)
-                    .map_err(|_|Error::UnexpectedError)?;
-
-
-                // Check for member addition reference
-                if !members.is_empty() {
-                        // Update the Key Mangement
-                        let mut keys = vec![applicant];
-
-                        members.iter().for_each(|mem|{
-                            if mem.1 == MemberRole::Admin{
-                                keys.push(mem.0);
-                            }
-                        });
-
-                        let wallet_data = KeyManagement {
-                            admin: applicant,
-                            key_pointer: account_inner,
-                            allowed_keys: keys,
-                        };
-
-
-                        // Check if the AccountId does have a and individual profile
-                        // And hook it up in the main Team
-                        members.iter().for_each(|mem|{
-                            if self.individual_profile.contains(mem.0) {
-                                let acc_data = self.individual_profile.get(mem.0).unwrap();
-                                acc_data.clone().update_ref_team(account_inner,mem.clone().1).unwrap();
-
-                                Self::env().emit_event(UpdatedTeamMembership{
-                                    team_name: team_applicant_data.clone().name,
-                                    team_id: team_applicant_data.clone().account_id,
-                                    individual_id: acc_data.account_id,
-                                    time
-                                });
-
-
-                                let _team_applicant_val_bytes = self.team_applicant_profile.insert(wallet_data.key_pointer,&team_applicant_data);
-                                
-                                // update all team account keys
-                                self.all_applicant_teams.push((team_applicant_data.clone().name,wallet_data.key_pointer));
-
-                                // Register Keys
-                                self.manage_keys.push(wallet_data.clone());
-                                // Emits an event
-                                Self::env().emit_event(TeamApplicantCreated{
-                                    name:team_applicant_data.clone().name,
-                                    account: team_applicant_data.clone().account_id,
-                                    time,
-                                });
-
-                            }else{
-                                let _applicant_val_bytes = self.team_applicant_profile.insert(wallet_data.key_pointer,&team_applicant_data);
-                                
-                                // update all team account keys
-                                self.all_applicant_teams.push((team_applicant_data.clone().name,wallet_data.key_pointer));
-
-                                // Register Keys
-                                self.manage_keys.push(wallet_data.clone());
-                                // Emits an event
-                                Self::env().emit_event(TeamApplicantCreated{
-                                    name:team_applicant_data.clone().name,
-                                    account: team_applicant_data.clone().account_id,
-                                    time,
-                                });
-                                
-                            }
-                        });
-
-                        Ok(())
-                        
-                    }else{
+            let amount = escrow.remaining();
+            if amount == 0 {
+                return Err(Error::EscrowNotFound)
+            }
+            escrow.reclaimed = escrow.reclaimed.saturating_add(amount);
+            self.escrows.insert((owner,proposal_id),&escrow);
 
-                        let wallet_data = KeyManagement {
-                            admin: applicant,
-                            key_pointer: account_inner,
-                            allowed_keys: vec![applicant],
-                        };
-
-                        let _applicant_val_bytes = self.team_applicant_profile.insert(wallet_data.key_pointer,&team_applicant_data);
-                        
-                        // update all team account keys
-                        self.all_applicant_teams.push((team_applicant_data.clone().name,wallet_data.key_pointer));
-
-                        // Register Keys
-                        self.manage_keys.push(wallet_data);
-                        // Emits an event
-                        Self::env().emit_event(TeamApplicantCreated{
-                            name:team_applicant_data.clone().name,
-                            account: team_applicant_data.clone().account_id,
-                            time,
-                        });
-                        Ok(())
+            Self::env().transfer(caller,amount).map_err(|_| Error::TransferFailed)?;
 
-                    }
-                
+            Self::env().emit_event(EscrowReclaimed{
+                owner,
+                project: proposal_id,
+                to: caller,
+                amount
+            });
+            Ok(())
+        }
 
-            } else {
-                // Check if account Exists
-                if self.team_applicant_profile.contains(applicant){
-                   return  Err(Error::AccountExists)
-                }
-                // If no account provided, applicant will be used.
-                let team_applicant_data = TeamApplicantProfile::new(name,description,mission,applicant,time,categories,chain,members.clone(),links)
-                    .map_err(|_|Error::UnexpectedError)?;
-
-                // Check for member addition reference
-                if !members.is_empty() {
-                        // Update the Key Mangement
-                        let mut keys = vec![applicant];
-
-                        members.iter().for_each(|mem|{
-                            if mem.1 == MemberRole::Admin{
-                                keys.push(mem.0);
-                            }
-                        });
-
-                        let wallet_data = KeyManagement {
-                            admin: applicant,
-                            key_pointer: applicant,
-                            allowed_keys: keys,
-                        };
-
-
-                        // Check if the AccountId does have an individual profile
-                        members.iter().for_each(|mem|{
-                            if self.individual_profile.contains(mem.0) {
-                                let mut acc_data = self.individual_profile.get(mem.0).unwrap();
-                                acc_data.update_ref_team(applicant,mem.clone().1).unwrap();
-
-                                Self::env().emit_event(UpdatedTeamMembership{
-                                    team_name: team_applicant_data.clone().name,
-                                    team_id: team_applicant_data.clone().account_id,
-                                    individual_id: acc_data.account_id,
-                                    time
-                                });
-
-
-                                let _applicant_val_bytes = self.team_applicant_profile.insert(wallet_data.key_pointer,&team_applicant_data);
-                                
-                                // update all team account keys
-                                self.all_applicant_teams.push((team_applicant_data.clone().name,wallet_data.key_pointer));
-
-                                // Register Keys
-                                self.manage_keys.push(wallet_data.clone());
-                                // Emits an event
-                                Self::env().emit_event(TeamApplicantCreated{
-                                    name:team_applicant_data.clone().name,
-                                    account: team_applicant_data.clone().account_id,
-                                    time,
-                                });
-
-                            }else{
-                                let _applicant_val_bytes = self.team_applicant_profile.insert(wallet_data.key_pointer,&team_applicant_data);
-                                
-                                // update all team account keys
-                                self.all_applicant_teams.push((team_applicant_data.clone().name,wallet_data.key_pointer));
-
-                                // Register Keys
-                                self.manage_keys.push(wallet_data.clone());
-                                // Emits an event
-                                Self::env().emit_event(TeamApplicantCreated{
-                                    name:team_applicant_data.clone().name,
-                                    account: team_applicant_data.clone().account_id,
-                                    time,
-                                });
-                            }
-                        });
-
-                        Ok(())
-                    }else{
+    }
 
-                        let wallet_data = KeyManagement {
-                            admin: applicant,
-                            key_pointer: applicant,
-                            allowed_keys: vec![applicant],
-                        };
 
-                        let _applicant_val_bytes = self.team_applicant_profile.insert(wallet_data.key_pointer,&team_applicant_data);
-                       
-                        // update all team account keys
-                        self.all_applicant_teams.push((team_applicant_data.clone().name,wallet_data.key_pointer));
 
-                        // Register Keys
-                        self.manage_keys.push(wallet_data);
-                        // Emits an event
-                        Self::env().emit_event(TeamApplicantCreated{
-                            name:team_applicant_data.clone().name,
-                            account: team_applicant_data.clone().account_id,
-                            time,
-                        });
+    impl GrantReview for OrdumState {
 
-                        Ok(())
+        #[ink(message,selector = 0xC0DE0046)]
+        fn set_rubric(&mut self,issuer_id: u16,criteria: Vec<Criterion>) -> ApplicationResult<()> {
+            self.authorize_issuer(Self::env().caller(),issuer_id)?;
 
-                    }
-                
+            if criteria.is_empty() || criteria.iter().any(|criterion| criterion.weight == 0) {
+                return Err(Error::InvalidRubric)
             }
+            self.rubrics.insert(issuer_id,&criteria);
 
-
+            Self::env().emit_event(RubricUpdated{
+                issuer_id,
+                criteria
+            });
+            Ok(())
         }
 
 
+        #[ink(message,selector = 0xC0DE0047)]
+        fn assign_reviewers(&mut self,issuer_id: u16,application_id: u32,reviewers: Vec<AccountId>) -> ApplicationResult<()> {
+            self.authorize_issuer(Self::env().caller(),issuer_id)?;
+            let application = self.queue_applications.get((issuer_id,application_id)).ok_or(Error::ApplicationNotFound)?;
+            let rubric = self.rubrics.get(issuer_id).ok_or(Error::RubricNotFound)?;
 
-        #[ink(message, selector = 0xC0DE0004)]
-        fn update_keys(&mut self, account: AccountId, action: KeyAction) -> CreateResult<()> {
-            let caller = Self::env().caller();
-            // check if the account is registered as admin
-            // Iterating over KeyManagement object and checking admin value
-            let result = self.manage_keys.clone().into_iter().find_map(|wallet| {
-                if wallet.admin == caller {
-                    Some(wallet)
-                } else {
-                    None
-                }
-            });
+            if reviewers.iter().any(|reviewer| self.is_conflicted(*reviewer,application.applicant,(issuer_id,application_id))) {
+                return Err(Error::ConflictOfInterest)
+            }
 
-            match result {
-                Some(mut acc_manage) => {
-                   acc_manage.update_keys_inner(account,action)?;
-                    Ok(())
+            let mut round = match self.reviews.get((issuer_id,application_id)) {
+                // Decided rounds are replaced once the application is submitted again
+                Some(round) if round.decision.is_none() => round,
+                _ => ReviewRound {
+                    reviewers: vec![],
+                    rubric,
+                    reviews: vec![],
+                    decision: None
+                }
+            };
+            round.reviews.retain(|review| reviewers.contains(&review.reviewer));
+            round.reviewers = reviewers.clone();
+            self.reviews.insert((issuer_id,application_id),&round);
+
+            match application.status {
+                Status::Submitted => {
+                    self.change_status(application.applicant,application.proposal,Status::UnderReview,Actor::Issuer)?
                 },
-                None => Err(Error::NotAuthorized)
+                Status::UnderReview => {},
+                _ => Err(Error::InvalidTransition)?
             }
+
+            Self::env().emit_event(ReviewersAssigned{
+                issuer_id,
+                id: application_id,
+                reviewers
+            });
+            Ok(())
         }
-       
-    }
 
 
+        #[ink(message,selector = 0xC0DE0048)]
+        fn submit_review(&mut self,issuer_id: u16,application_id: u32,scores: Vec<u8>,comments: String) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let application = self.queue_applications.get((issuer_id,application_id)).ok_or(Error::ApplicationNotFound)?;
+            let mut round = self.reviews.get((issuer_id,application_id)).ok_or(Error::NotReviewer)?;
 
-    impl Proposer for OrdumState {
+            if !round.reviewers.contains(&caller) {
+                return Err(Error::NotReviewer)
+            }
+            // The reviewer may have joined the applicant team after the assignment
+            if self.is_conflicted(caller,application.applicant,(issuer_id,application_id)) {
+                return Err(Error::ConflictOfInterest)
+            }
+            if application.status != Status::UnderReview || round.decision.is_some() {
+                return Err(Error::InvalidTransition)
+            }
+            if scores.len() != round.rubric.len() || scores.iter().any(|score| *score > MAX_SCORE) {
+                return Err(Error::InvalidScores)
+            }
 
-        #[ink(message, selector = 0xC0DE0014)]
-        fn add_proposal(&mut self,chain:Chains,ref_no:Option<u32>,file:String,mem:u32) -> MilestoneResult<()>{
+            round.reviews.retain(|review| review.reviewer != caller);
+            round.reviews.push(Review{
+                reviewer: caller,
+                scores,
+                comments,
+                submitted: Self::env().block_timestamp()
+            });
+            self.reviews.insert((issuer_id,application_id),&round);
 
+            Self::env().emit_event(ReviewSubmitted{
+                issuer_id,
+                id: application_id,
+                reviewer: caller
+            });
+            Ok(())
+        }
+
+
+        #[ink(message,selector = 0xC0DE0049)]
+        fn declare_conflict(&mut self,issuer_id: u16,application_id: u32,reason: String) -> ApplicationResult<()> {
             let caller = Self::env().caller();
-            // Check if the caller has a profile account
-            if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                key.allowed_keys.contains(&caller)
-            }){
+            let round = self.reviews.get((issuer_id,application_id)).ok_or(Error::NotReviewer)?;
+            if !round.reviewers.contains(&caller) {
+                return Err(Error::NotReviewer)
+            }
+            self.add_conflict((issuer_id,application_id),caller,reason,false)
+        }
 
-                // Check if there is existing Projects
-                if let Some(mut projects) = self.proposal.get(wallet.key_pointer){
 
-                    let no_projects = projects.len() as u8;
-                    // Build the Project Object and InnerProject
-                    let inner_project = InnerProject {
-                        chain,
-                        file,
-                        referenda_no:ref_no
-                    };
+        #[ink(message,selector = 0xC0DE0050)]
+        fn recuse(&mut self,issuer_id: u16,application_id: u32,reason: String) -> ApplicationResult<()> {
+            let caller = Self::env().caller();
+            let mut round = self.reviews.get((issuer_id,application_id)).ok_or(Error::NotReviewer)?;
+            if !round.reviewers.contains(&caller) {
+                return Err(Error::NotReviewer)
+            }
+            if round.decision.is_some() {
+                return Err(Error::InvalidTransition)
+            }
+            self.add_conflict((issuer_id,application_id),caller,reason,true)?;
 
-                    let project = Project::new(no_projects,inner_project,mem);
+            round.reviewers.retain(|reviewer| *reviewer != caller);
+            round.reviews.retain(|review| review.reviewer != caller);
+            self.reviews.insert((issuer_id,application_id),&round);
+            Ok(())
+        }
 
-                    projects.push(project);
+    }
 
-                    // update Team application count
-                    let mut team_profile = self.team_applicant_profile.get(wallet.key_pointer).unwrap();
 
-                    team_profile.update_applications_count();
-                    
-                }else{
-                    
-                    let inner_project = InnerProject {
-                        chain,
-                        file,
-                        referenda_no:ref_no
-                    };
 
-                    let project = Project::new(0,inner_project,mem);
+    impl Proposer for OrdumState {
 
-                    self.proposal.insert(wallet.key_pointer,&vec![project]);
+        #[ink(message, selector = 0xC0DE0014)]
+        fn add_proposal(&mut self,team:Option<AccountId>,chain:Chains,ref_no:Option<u32>,file:String,mem:u32) -> MilestoneResult<()>{
 
-                    // update Team application count
-                    let mut team_profile = self.team_applicant_profile.get(wallet.key_pointer).unwrap();
+            let caller = Self::env().caller();
+            // Check if the caller has a profile account
+            if let Some(wallet) = self.authorize(caller,team,Capability::SubmitProposal){
 
-                    team_profile.update_applications_count();
+                // Teams requiring several approvals queue the submission
+                if wallet.threshold > 1 {
+                    self.queue_operation(&wallet,caller,TeamOperation::SubmitProposal{ chain, ref_no, file, mem });
+                }else{
+                    self.add_team_proposal(wallet.key_pointer,chain,ref_no,file,mem)?
                 }
 
+            }else if team.is_some(){
+                // The caller is not allowed on the specified team
+                Err(MilestoneError::NotAuthorized)?
             }else{
 
                 // Check if there is an individual profile and update application count
                 if let Some(mut individual) = self.individual_profile.get(caller) {
-                     // Check if there is existing Projects
-                        if let Some(mut projects) = self.proposal.get(caller){
-
-                            let no_projects = projects.len() as u8;
-                            // Build the Project Object and InnerProject
-                            let inner_project = InnerProject {
-                                chain,
-                                file,
-                                referenda_no:ref_no
-                            };
-
-                            let project = Project::new(no_projects,inner_project,mem);
-
-                            projects.push(project);
-
-                            individual.update_applications_count();
-                            
-                        }else{
-                            
-                            let inner_project = InnerProject {
-                                chain,
-                                file,
-                                referenda_no:ref_no
-                            };
-
-                            let project = Project::new(0,inner_project,mem);
+                    self.store_proposal(caller,chain,ref_no,file,mem)?;
 
-                            self.proposal.insert(caller,&vec![project]);
-
-                            individual.update_applications_count();
-                        }
+                    individual.update_applications_count();
+                    self.individual_profile.insert(caller,&individual);
                 }else{
                     Err(MilestoneError::NotAuthorized)?
                 }
@@ -1238,18 +5345,16 @@ mod ordum {
 
 
         #[ink(message, selector = 0xC0DE0015)]
-        fn fetch_proposal(&self,proposal_id:u8) -> MilestoneResult<Project>{
+        fn fetch_proposal(&self,team:Option<AccountId>,proposal_id:u8) -> MilestoneResult<Project>{
 
             let caller = Self::env().caller();
             // Check if the caller has a profile account
-            if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                key.allowed_keys.contains(&caller)
-            }){
+            if let Some(wallet) = self.authorize(caller,team,Capability::ViewPrivateData){
 
-                let projects = self.proposal.get(wallet.key_pointer).ok_or(MilestoneError::ProjectNotFound)?;
+                let projects = self.projects.get(wallet.key_pointer).ok_or(MilestoneError::ProjectNotFound)?;
                 if !projects.is_empty(){
 
-                    if let Some(project) = projects.get(proposal_id as usize - 1){
+                    if let Some(project) = (proposal_id as usize).checked_sub(1).and_then(|index| projects.get(index)){
                         Ok(project.clone())
                     }else{
                         Err(MilestoneError::ProjectNotFound)
@@ -1259,14 +5364,17 @@ mod ordum {
                     Err(MilestoneError::ProjectNotFound)
                 }
 
+            }else if team.is_some(){
+                // The caller is not allowed on the specified team
+                Err(MilestoneError::NotAuthorized)
             }else{
                 // fetch for individual profile
                 if self.individual_profile.get(caller).is_some(){
 
-                    let projects = self.proposal.get(caller).ok_or(MilestoneError::ProjectNotFound)?;
+                    let projects = self.projects.get(caller).ok_or(MilestoneError::ProjectNotFound)?;
                     if !projects.is_empty(){
 
-                        if let Some(project) = projects.get(proposal_id as usize - 1){
+                        if let Some(project) = (proposal_id as usize).checked_sub(1).and_then(|index| projects.get(index)){
                             Ok(project.clone())
                         }else{
                             Err(MilestoneError::ProjectNotFound)
@@ -1285,14 +5393,12 @@ mod ordum {
 
 
         #[ink(message, selector = 0xC0DE0019)]
-        fn fetch_latest_proposal_id(&self) -> MilestoneResult<u8>{
+        fn fetch_latest_proposal_id(&self,team:Option<AccountId>) -> MilestoneResult<u8>{
             let caller = Self::env().caller();
             // Check if there is proposal in the registered user Id
-            if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                key.allowed_keys.contains(&caller)
-            }){
+            if let Some(wallet) = self.authorize(caller,team,Capability::ViewPrivateData){
                 // Check for Team
-                let project = self.proposal.get(wallet.key_pointer).ok_or(MilestoneError::ProjectNotFound)?;
+                let project = self.projects.get(wallet.key_pointer).ok_or(MilestoneError::ProjectNotFound)?;
                 
                 if let Some(last_project) = project.last(){
                     let last_project_id = last_project.id;
@@ -1302,9 +5408,12 @@ mod ordum {
                     Ok(0)
                 } 
 
+            }else if team.is_some(){
+                // The caller is not allowed on the specified team
+                Err(MilestoneError::NotAuthorized)
             }else{
                 // Check for individual
-                let project = self.proposal.get(caller).ok_or(MilestoneError::ProjectNotFound)?;
+                let project = self.projects.get(caller).ok_or(MilestoneError::ProjectNotFound)?;
                 if let Some(last_project) = project.last(){
                     let last_project_id = last_project.id;
                     Ok(last_project_id)
@@ -1322,286 +5431,207 @@ mod ordum {
     impl MilestoneTracker for OrdumState {
        
         #[ink(message, selector = 0xC0DE0010)]
-        fn add_milestone(&mut self,project_id:u8,file:String,mem:u32) -> MilestoneResult<()>{
+        fn add_milestone(&mut self,team:Option<AccountId>,project_id:u8,file:String,mem:u32,terms:MilestoneTerms) -> MilestoneResult<()>{
 
             let caller = Self::env().caller();
             // Check if the caller has a profile account
-            if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                key.allowed_keys.contains(&caller)
-            }){
-                
-                
-                // Check if there id a registered project
-                if let Some(projects) = self.proposal.get(wallet.key_pointer){
-                    let mut current_project = projects[project_id as usize -1].clone();
-                    
-                    // Check if there are milestones
-                    if let Some(mile) = current_project.main.last(){
-                        // Build a Milestone
-                        let current_main_index = current_project.main.len() as u8;
-                        
-                        let milestone = AddMilestone::new(current_main_index +1, mile.no_edits, file, mem);
-                        current_project.add_main(milestone, mem)?
+            let owner = self.owner_of(caller,team,Capability::EditMilestone)?;
 
-                    }else{
+            self.update_project(owner,project_id,|current_project,quota|{
+                // Build a Milestone following the current path
+                let main_index = u8::try_from(current_project.active_path().len())
+                    .ok().and_then(|len| len.checked_add(1))
+                    .ok_or(MilestoneError::MaxMilestonesExceeded)?;
+                let milestone = AddMilestone::new(main_index, 0, file, mem, terms);
 
-                        let milestone = AddMilestone::new(1, 0, file, mem);
-                        current_project.add_main(milestone, mem)?
-                    }
-                   Ok(())? 
-                }else{
-                    Err(MilestoneError::ProjectNotFound)?
+                current_project.add_main(milestone.clone(), mem, quota)?;
+                Self::notify_overdue(owner,project_id,&milestone);
+                Ok(())
+            })
+        }
+    
+    
+
+        #[ink(message, selector = 0xC0DE0011)]
+        fn edit_milestone(&mut self,team:Option<AccountId>,project_id:u8,mile_no:u8,file:String,mem:u32) -> MilestoneResult<()>{
+
+            let caller = Self::env().caller();
+            // Check if the caller has a profile account
+            let owner = self.owner_of(caller,team,Capability::EditMilestone)?;
+
+            self.update_project(owner,project_id,|current_project,quota|{
+                // Only main milestones of the active path are edited
+                if current_project.is_replaced(mile_no) {
+                    return Err(MilestoneError::MilestoneReplaced)
+                }
+                // get the latest no of edits in the specified milestone
+                let specified_mile = (mile_no as usize).checked_sub(1)
+                    .and_then(|index| current_project.main.get(index))
+                    .ok_or(MilestoneError::MilestoneNotFound)?;
+                // Delivered milestones can't be changed
+                if matches!(specified_mile.state,MilestoneState::Submitted | MilestoneState::Accepted) {
+                    return Err(MilestoneError::InvalidState)
                 }
-            }else{
-                // Check if there is an individual profile and update application count
-                if self.individual_profile.get(caller).is_some(){
 
-                     // Check if there id a registered project
-                    if let Some(projects) = self.proposal.get(caller){
-                        let mut current_project = projects[project_id as usize -1].clone();
-                        
-                        // Check if there are milestones
-                        if let Some(mile) = current_project.main.last(){
-                            // Build a Milestone
-                            let current_main_index = current_project.main.len() as u8;
-                            
-                            let milestone = AddMilestone::new(current_main_index +1, mile.no_edits, file, mem);
-                            current_project.add_main(milestone, mem)?
+                // Build the edit milestone object
+                let edited_milestone = EditedMile::new(
+                    specified_mile.no_edits.checked_add(1).ok_or(MilestoneError::MaxEditsExceeded)?,
+                    mile_no,file,mem
+                );
+                // Store it
+                current_project.add_edit(mile_no, edited_milestone, mem, quota)?;
 
-                        }else{
+                let milestone = current_project.milestone_mut(mile_no).ok_or(MilestoneError::MilestoneNotFound)?;
+                Self::notify_overdue(owner,project_id,milestone);
+                Ok(())
+            })
+        }
+    
 
-                            let milestone = AddMilestone::new(1, 0, file, mem);
-                            current_project.add_main(milestone, mem)?
-                        }
-                    Ok(())? 
+        #[ink(message, selector = 0xC0DE0012)]
+        fn pivote_milestone(&mut self,team:Option<AccountId>,project:u8,mile_no:u8,reason:String,file:String,mem:u32,terms:MilestoneTerms) -> MilestoneResult<()>{
 
-                    }else{
-                        Err(MilestoneError::ProjectNotFound)?
-                    }
+            let caller = Self::env().caller();
+            if reason.is_empty() {
+                Err(MilestoneError::PivotReasonRequired)?
+            }
+            // Check if the caller has a profile account
+            if let Some(wallet) = self.authorize(caller,team,Capability::EditMilestone){
 
+                // Teams requiring several approvals queue the pivot
+                if wallet.threshold > 1 {
+                    self.queue_operation(&wallet,caller,TeamOperation::PivotMilestone{ project, mile_no, reason, file, mem, terms });
                 }else{
-                    Err(MilestoneError::NotAuthorized)?
+                    self.pivot(wallet.key_pointer,project,mile_no,reason,file,mem,terms)?
                 }
+
+            }else if team.is_some(){
+                // The caller is not allowed on the specified team
+                Err(MilestoneError::NotAuthorized)?
+            }else if self.individual_profile.contains(caller){
+                self.pivot(caller,project,mile_no,reason,file,mem,terms)?
+            }else{
+                Err(MilestoneError::NotAuthorized)?
             }
             Ok(())
         }
     
-    
 
-        #[ink(message, selector = 0xC0DE0011)]
-        fn edit_milestone(&mut self,project_id:u8,mile_no:u8,file:String,mem:u32) -> MilestoneResult<()>{
+        
+        #[ink(message, selector = 0xC0DE0052)]
+        fn update_deliverable(&mut self,team:Option<AccountId>,project_id:u8,mile_no:u8,deliverable:u8,status:DeliverableStatus) -> MilestoneResult<()>{
 
             let caller = Self::env().caller();
+            // Check if the caller has a profile account
+            let owner = self.owner_of(caller,team,Capability::EditMilestone)?;
 
-             // Check if the caller has a profile account
-             if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                key.allowed_keys.contains(&caller)
-            }){
-                
-
-                 // Check if there id a registered project
-                 if let Some(projects) = self.proposal.get(wallet.key_pointer){
-                    let mut current_project = projects[project_id as usize -1].clone();
-                    
-                    // Check if there are milestones
-                   if current_project.main.is_empty(){
-                        Err(MilestoneError::MilestoneNotFound)?
-                   }
-                   // get the latest no of edits in the specified milestone
-                   let specified_mile = current_project.main.get(mile_no as usize -1).ok_or(MilestoneError::MilestoneNotFound)?;
-        
-                   // Build the edit milestone object
-                   let edited_milestone = EditedMile::new(specified_mile.no_edits + 1,mile_no,file,mem);
-                   // Store it
-                   current_project.add_edit(mile_no, edited_milestone, mem)?;
-                   Ok(())? 
-                }else{
-                    Err(MilestoneError::ProjectNotFound)?
+            self.update_project(owner,project_id,|current_project,_quota|{
+                let milestone = current_project.milestone_mut(mile_no).ok_or(MilestoneError::MilestoneNotFound)?;
+                if matches!(milestone.state,MilestoneState::Submitted | MilestoneState::Accepted) {
+                    return Err(MilestoneError::InvalidState)
                 }
+                milestone.deliverables.get_mut(deliverable as usize)
+                    .ok_or(MilestoneError::DeliverableNotFound)?
+                    .status = status;
 
-            }else{
-                // Check for individual profile
-                if self.individual_profile.get(caller).is_some(){
+                Self::notify_overdue(owner,project_id,milestone);
+                Ok(())
+            })
+        }
 
-                    // Check if there id a registered project
-                    if let Some(projects) = self.proposal.get(caller){
-                        let mut current_project = projects[project_id as usize -1].clone();
-                        
-                        // Check if there are milestones
-                    if current_project.main.is_empty(){
-                            Err(MilestoneError::MilestoneNotFound)?
-                    }
-                    // get the latest no of edits in the specified milestone
-                    let specified_mile = current_project.main.get(mile_no as usize -1).ok_or(MilestoneError::MilestoneNotFound)?;
-            
-                    // Build the edit milestone object
-                    let edited_milestone = EditedMile::new(specified_mile.no_edits + 1,mile_no,file,mem);
-                    // Store it
-                    current_project.add_edit(mile_no, edited_milestone, mem)?;
-                    Ok(())? 
 
-                    }else{
-                        Err(MilestoneError::ProjectNotFound)?
-                    }
+        #[ink(message, selector = 0xC0DE0053)]
+        fn submit_milestone_delivery(&mut self,team:Option<AccountId>,project_id:u8,mile_no:u8,evidence:String) -> MilestoneResult<()>{
 
-                }else{
-                    Err(MilestoneError::NotAuthorized)?
-                }
-            }            
+            let caller = Self::env().caller();
+            // Check if the caller has a profile account
+            let owner = self.owner_of(caller,team,Capability::EditMilestone)?;
 
-            Ok(())
-        }
-    
+            self.update_project(owner,project_id,|current_project,_quota|{
+                if !matches!(current_project.status,Status::Approved | Status::Funded) {
+                    return Err(MilestoneError::NotApproved)
+                }
+                let milestone = current_project.milestone_mut(mile_no).ok_or(MilestoneError::MilestoneNotFound)?;
+                milestone.submit(evidence.clone())?;
 
-        #[ink(message, selector = 0xC0DE0012)]
-        fn pivote_milestone(&mut self,_project:u8,_mile_no:u8,_file:String,_mem:u32) -> MilestoneResult<()>{
+                Self::notify_overdue(owner,project_id,milestone);
+                Ok(())
+            })?;
 
-            let _caller = Self::env().caller();
+            Self::env().emit_event(MilestoneSubmitted{
+                owner,
+                project: project_id,
+                mile_no,
+                evidence
+            });
             Ok(())
         }
-    
-
-        
-        #[ink(message, selector = 0xC0DE0013)]
-        fn fetch_milestone(&self,project_id:u8,mile_no:Option<u8>) -> MilestoneResult<FetchedMilestone>{
-
-            let caller = Self::env().caller();
 
-            // FetchMilestone Object
-            let mut result_milestone = FetchedMilestone::default();
 
-             // Check if the caller has a profile account
-             if let Some(wallet) = self.manage_keys.iter().find(|&key|{
-                key.allowed_keys.contains(&caller)
-            }){
+        #[ink(message, selector = 0xC0DE0055)]
+        fn evaluate_milestone(&mut self,owner:AccountId,project_id:u8,mile_no:u8,state:MilestoneState,comments:Option<String>) -> MilestoneResult<()>{
 
-                
-                // Check if the projects are there
-                if let Some(projects) = self.proposal.get(wallet.key_pointer){
-                    // Check if the specific project is there
-                    if let Some(project) = projects.get(project_id as usize - 1){
-
-                        // Check if the milestone is there
-                        if project.main.is_empty() {
-                            Err(MilestoneError::MilestoneNotFound)?
-                        }
+            let caller = Self::env().caller();
+            let projects = self.projects.get(owner).ok_or(MilestoneError::ProjectNotFound)?;
+            let project = (project_id as usize).checked_sub(1)
+                .and_then(|index| projects.get(index))
+                .ok_or(MilestoneError::ProjectNotFound)?;
+
+            // The issuer of the project's application or one of its reviewers evaluates
+            let application = project.application.ok_or(MilestoneError::NotAuthorized)?;
+            let is_issuer = self.authorize_issuer(caller,application.0).is_ok();
+            let declared = self.conflicts.get(application).unwrap_or_default().iter()
+                .any(|conflict| conflict.reviewer == caller);
+            let is_reviewer = self.reviews.get(application)
+                .map_or(false,|round| round.reviewers.contains(&caller))
+                && !declared && !self.is_conflicted(caller,owner,application);
+            if !is_issuer && !is_reviewer {
+                return Err(MilestoneError::NotAuthorized)
+            }
+            // Reviewers only advise, accepting pays the milestone and rejecting ends it
+            if !is_issuer && state != MilestoneState::ChangesRequested {
+                return Err(MilestoneError::NotAuthorized)
+            }
 
-                        // Check if specific milestone is given
-                        if let Some(m_no) = mile_no{
-                            // Fetch the milestone in the main and the edits
-                            if let Some(mile) = project.main.get(m_no as usize -1){
+            let mut amount = 0;
+            let ask = project.program.map(|_| project.ask);
+            self.update_project(owner,project_id,|current_project,_quota|{
+                let milestone = current_project.milestone_mut(mile_no).ok_or(MilestoneError::MilestoneNotFound)?;
+                milestone.evaluate(state.clone(),comments)?;
+                amount = milestone.amount;
+                Ok(())
+            })?;
 
-                                // Fetch the edits associated with the milestone
-                                let edit = project.edited.iter().find(|&v| v.0 == m_no);
-                                if edit.is_some(){
-                                    result_milestone.main = Some(vec![mile.clone()]); 
-                                    result_milestone.edited_per_mile = Some(edit.ok_or(MilestoneError::UnexpectedError)?.1.clone());
-                                }
-                                // If there are no edits
-                                result_milestone.main = Some(vec![mile.clone()]); 
+            if state == MilestoneState::Accepted {
+                self.release(owner,project_id,mile_no,amount,ask,application.0)?;
+            }
 
-                            }else{
-                                Err(MilestoneError::MilestoneNotFound)?
-                            }  
+            Self::env().emit_event(MilestoneEvaluated{
+                owner,
+                project: project_id,
+                mile_no,
+                evaluator: caller,
+                state
+            });
+            Ok(())
+        }
 
-                        }else{
-                            // Construct a fetchedMilestone object to fetch whole tree of milestone nodes
-                            result_milestone.main = Some(project.main.clone());
-                            // Fetch all the edits per milestons
-                            // -- check in the edited section if there is a main_index value and push it
-
-                            //NOTE: We can optimize here as for now the algorithm is searching the whole edited vector and it doest need to; 0(N^N)
-                            let mut edits_value:Vec<(u8,Vec<EditedMile>)> = Vec::new();
-
-                            project.main.iter().for_each(|m|{
-                                project.edited.iter().for_each(|edit|{
-                                   if edit.0 == m.main_index{
-                                        edits_value.push(edit.clone())
-                                   }
-                                })
-                            });
-                            // Check if the edits_value contain any value if not the leave the ResultMilestone as it is;
-                            if !edits_value.is_empty(){
-                                result_milestone.all_edits = Some(edits_value);
-                            }
-                        }
 
-                    }else{
-                        Err(MilestoneError::ProjectNotFound)?
-                    }     
-                    
-                }else{
-                    Err(MilestoneError::ProjectNotFound)?
-                }
-            }else{
-                // fetch for individual profile
-                if self.individual_profile.get(caller).is_some(){
+        #[ink(message, selector = 0xC0DE0013)]
+        fn fetch_milestone(&self,team:Option<AccountId>,project_id:u8,mile_no:Option<u8>) -> MilestoneResult<FetchedMilestone>{
 
-                    // Check if the projects are there
-                    if let Some(projects) = self.proposal.get(caller){
-                        // Check if the specific project is there
-                        if let Some(project) = projects.get(project_id as usize - 1){
-
-                            // Check if the milestone is there
-                            if project.main.is_empty() {
-                                Err(MilestoneError::MilestoneNotFound)?
-                            }
-
-                            // Check if specific milestone is given
-                            if let Some(m_no) = mile_no{
-                                // Fetch the milestone in the main and the edits
-                                if let Some(mile) = project.main.get(m_no as usize -1){
-
-                                    // Fetch the edits associated with the milestone
-                                    let edit = project.edited.iter().find(|&v| v.0 == m_no);
-                                    if edit.is_some(){
-                                        result_milestone.main = Some(vec![mile.clone()]); 
-                                        result_milestone.edited_per_mile = Some(edit.ok_or(MilestoneError::UnexpectedError)?.1.clone());
-                                    }
-                                    // If there are no edits
-                                    result_milestone.main = Some(vec![mile.clone()]); 
-
-                                }else{
-                                    Err(MilestoneError::MilestoneNotFound)?
-                                }  
-
-                            }else{
-                                // Construct a fetchedMilestone object to fetch whole tree of milestone nodes
-                                result_milestone.main = Some(project.main.clone());
-                                // Fetch all the edits per milestons
-                                // -- check in the edited section if there is a main_index value and push it
-
-                                //NOTE: We can optimize here as for now the algorithm is searching the whole edited vector and it doest need to; 0(N^N)
-                                let mut edits_value:Vec<(u8,Vec<EditedMile>)> = Vec::new();
-
-                                project.main.iter().for_each(|m|{
-                                    project.edited.iter().for_each(|edit|{
-                                    if edit.0 == m.main_index{
-                                            edits_value.push(edit.clone())
-                                    }
-                                    })
-                                });
-                                // Check if the edits_value contain any value if not the leave the ResultMilestone as it is;
-                                if !edits_value.is_empty(){
-                                    result_milestone.all_edits = Some(edits_value);
-                                }
-                            }
+            let caller = Self::env().caller();
+            // Check if the caller has a profile account
+            let owner = self.owner_of(caller,team,Capability::ViewPrivateData)?;
 
-                        }else{
-                            Err(MilestoneError::ProjectNotFound)?
-                        }     
-                        
-                    }else{
-                        Err(MilestoneError::ProjectNotFound)?
-                    }
+            // Check if the projects are there
+            let projects = self.projects.get(owner).ok_or(MilestoneError::ProjectNotFound)?;
+            // Check if the specific project is there
+            let project = (project_id as usize).checked_sub(1)
+                .and_then(|index| projects.get(index))
+                .ok_or(MilestoneError::ProjectNotFound)?;
 
-                }else{
-                    Err(MilestoneError::NotAuthorized)?
-                }
-                
-            }
-            Ok(result_milestone)
-                  
+            project.fetch(mile_no)
         }
     }
 
@@ -1663,6 +5693,896 @@ mod ordum {
             }
     }
 
+    //  ---------------------------------------- UNIT TESTING ------------------------------------------------- //
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{test,DefaultEnvironment};
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(account: AccountId) {
+            test::set_caller::<DefaultEnvironment>(account);
+        }
+
+        type Event = <OrdumState as ink::reflect::ContractEventBase>::Type;
+
+        /// Events emitted so far, decoded
+        fn emitted() -> Vec<Event> {
+            test::recorded_events()
+                .map(|event| <Event as scale::Decode>::decode(&mut &event.data[..]).unwrap())
+                .collect()
+        }
+
+        /// Contract with an individual profile for alice and a team administered by bob
+        fn setup() -> OrdumState {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut ordum = OrdumState::new();
+
+            ordum.create_individual_profile(
+                "Alice".into(),
+                "Builder".into(),
+                vec![Categories::Defi],
+                vec![Chains::Polkadot],
+                vec![],
+                UserRole::Applicant
+            ).unwrap();
+
+            set_caller(accounts.bob);
+            ordum.create_applicant_profile(
+                "Ordum".into(),
+                None,
+                "Grant tooling".into(),
+                "Track funded work".into(),
+                vec![Categories::Infrastructure],
+                vec![Chains::Kusama],
+                vec![],
+                vec![]
+            ).unwrap();
+
+            ordum
+        }
+
+        #[ink::test]
+        fn profile_updates_report_the_changed_fields() {
+            let accounts = accounts();
+            let mut ordum = setup();
+
+            set_caller(accounts.alice);
+            ordum.update_individual_profile(Some("Builder".into()),None,None,None,None).unwrap();
+            ordum.update_individual_profile(Some("Auditor".into()),None,Some(vec![Chains::Polkadot]),None,None).unwrap();
+
+            set_caller(accounts.bob);
+            ordum.update_applicant_profile(None,Some("Ordum".into()),None,None,None,None,None).unwrap();
+            ordum.update_applicant_profile(None,Some("Ordum Labs".into()),None,Some("Fund work".into()),None,None,None).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.update_applicant_profile(Some(accounts.bob),None,Some("spam".into()),None,None,None,None),Err(Error::NotAuthorized));
+
+            let updates: Vec<Vec<ProfileField>> = emitted().into_iter().filter_map(|event|{
+                match event {
+                    Event::IndividualProfileUpdated(update) => Some(update.fields),
+                    Event::TeamApplicantUpdated(update) => Some(update.fields),
+                    _ => None
+                }
+            }).collect();
+            assert_eq!(updates,vec![
+                vec![ProfileField::Description],
+                vec![ProfileField::Name,ProfileField::Mission]
+            ]);
+            assert_eq!(ordum.get_team_applicant_profile(Some(accounts.bob)).unwrap().name,"Ordum Labs");
+            let individual = ordum.individual_profile.get(accounts.alice).unwrap();
+            assert_eq!((individual.description,individual.chains),("Auditor".into(),vec![Chains::Polkadot]));
+            assert_eq!(ordum.update_individual_profile(Some("spam".into()),None,None,None,None),Err(Error::AccountDontExists));
+        }
+
+        #[ink::test]
+        fn team_keys_are_given_up_before_deleting_an_individual() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            // The team shares alice's account as its key pointer
+            ordum.create_applicant_profile(
+                "Alice Labs".into(),
+                None,
+                "Research".into(),
+                "Grants".into(),
+                vec![Categories::Defi],
+                vec![Chains::Polkadot],
+                vec![],
+                vec![]
+            ).unwrap();
+            ordum.add_proposal(Some(accounts.alice),Chains::Polkadot,None,"team-1".into(),10).unwrap();
+
+            assert_eq!(ordum.delete_individual_profile(ErasurePolicy::Archive),Err(Error::TeamKeysHeld));
+            assert!(ordum.individual_profile.contains(accounts.alice));
+            assert_eq!(ordum.projects.get(accounts.alice).unwrap().len(),1);
+        }
+
+        #[ink::test]
+        fn deleted_teams_leave_no_state_behind() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            let create_team = |ordum: &mut OrdumState| ordum.create_applicant_profile(
+                "Ordum".into(),None,"Grant tooling".into(),"Track funded work".into(),
+                vec![Categories::Infrastructure],vec![Chains::Kusama],vec![],vec![]
+            ).unwrap();
+
+            set_caller(accounts.bob);
+            ordum.add_proposal(None,Chains::Kusama,None,"cid-1".into(),0).unwrap();
+            ordum.define_role(None,"Editor".into(),vec![Capability::EditMilestone]).unwrap();
+            ordum.invite_member(None,accounts.django,MemberRole::Regular,None).unwrap();
+            ordum.update_keys(None,accounts.charlie,KeyAction::ADD).unwrap();
+            ordum.update_keys(None,accounts.charlie,KeyAction::ChangeAdmin).unwrap();
+            ordum.delete_applicant_profile(None,ErasurePolicy::Archive).unwrap();
+
+            // The team registered again under the same account starts from scratch
+            create_team(&mut ordum);
+            assert!(ordum.get_team_roles(accounts.bob).unwrap().is_empty());
+            assert!(ordum.get_admin_handover(accounts.bob).is_err());
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.accept_admin(accounts.bob),Err(Error::HandoverNotFound));
+            set_caller(accounts.django);
+            assert!(ordum.get_invitations().unwrap().is_empty());
+
+            // Archived proposals of both deletions are kept
+            set_caller(accounts.bob);
+            ordum.add_proposal(None,Chains::Kusama,None,"cid-2".into(),0).unwrap();
+            ordum.delete_applicant_profile(None,ErasurePolicy::Archive).unwrap();
+            assert_eq!(ordum.get_archived_proposals(accounts.bob).unwrap().len(),2);
+        }
+
+        #[ink::test]
+        fn threshold_operations_are_approved_cancelled_and_executed() {
+            let accounts = accounts();
+            let mut ordum = setup();
+
+            set_caller(accounts.bob);
+            ordum.update_keys(None,accounts.charlie,KeyAction::ADD).unwrap();
+            ordum.update_keys(None,accounts.django,KeyAction::ADD).unwrap();
+            ordum.set_threshold(None,2).unwrap();
+
+            // Lowering the threshold now needs a second key
+            ordum.set_threshold(None,1).unwrap();
+            assert_eq!(ordum.key_wallets.get(accounts.bob).unwrap().threshold,2);
+            assert_eq!(ordum.approve_operation(None,1),Err(Error::AlreadyApproved));
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.cancel_operation(Some(accounts.bob),1),Err(Error::NotAuthorized));
+            set_caller(accounts.eve);
+            assert_eq!(ordum.approve_operation(Some(accounts.bob),1),Err(Error::NotAuthorized));
+
+            // The proposer cancels
+            set_caller(accounts.bob);
+            ordum.cancel_operation(None,1).unwrap();
+            assert!(ordum.get_pending_operations(accounts.bob).unwrap().is_empty());
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.approve_operation(Some(accounts.bob),1),Err(Error::OperationNotFound));
+
+            // And executes once another key approves
+            ordum.set_threshold(Some(accounts.bob),1).unwrap();
+            set_caller(accounts.django);
+            ordum.approve_operation(Some(accounts.bob),2).unwrap();
+            assert_eq!(ordum.key_wallets.get(accounts.bob).unwrap().threshold,1);
+            assert!(ordum.get_pending_operations(accounts.bob).unwrap().is_empty());
+        }
+
+        #[ink::test]
+        fn members_act_on_their_team_through_roles() {
+            let accounts = accounts();
+            let mut ordum = setup();
+
+            set_caller(accounts.bob);
+            ordum.add_proposal(None,Chains::Kusama,None,"team-1".into(),10).unwrap();
+            ordum.invite_member(None,accounts.alice,MemberRole::Regular,None).unwrap();
+            assert_eq!(ordum.assign_role(None,accounts.alice,MemberRole::Admin),Err(Error::InvalidRole));
+            assert_eq!(ordum.define_role(None,"Keys".into(),vec![Capability::ManageKeys]),Err(Error::InvalidRole));
+
+            set_caller(accounts.alice);
+            ordum.accept_invitation(accounts.bob).unwrap();
+            // Regular members edit the team's milestones without naming the team
+            ordum.add_milestone(None,1,"m1".into(),5,MilestoneTerms::default()).unwrap();
+            assert_eq!(ordum.projects.get(accounts.bob).unwrap()[0].main.len(),1);
+            assert_eq!(ordum.add_proposal(Some(accounts.bob),Chains::Kusama,None,"team-2".into(),10),Err(MilestoneError::NotAuthorized));
+            assert_eq!(ordum.set_max_keys(Some(accounts.bob),4),Err(Error::NotAuthorized));
+        }
+
+        #[ink::test]
+        fn expired_invitations_are_dropped() {
+            let accounts = accounts();
+            let mut ordum = setup();
+
+            set_caller(accounts.bob);
+            assert_eq!(ordum.team_applicant_profile.get(accounts.bob).unwrap().member_role(accounts.bob),Some(MemberRole::Admin));
+            ordum.invite_member(None,accounts.alice,MemberRole::Regular,Some(10)).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(100);
+            set_caller(accounts.alice);
+            ordum.accept_invitation(accounts.bob).unwrap();
+            assert!(ordum.get_invitations().unwrap().is_empty());
+            assert_eq!(ordum.team_applicant_profile.get(accounts.bob).unwrap().member_role(accounts.alice),None);
+            assert_eq!(ordum.accept_invitation(accounts.bob),Err(Error::InvitationNotFound));
+        }
+
+        #[ink::test]
+        fn invitations_can_be_declined() {
+            let accounts = accounts();
+            let mut ordum = setup();
+
+            set_caller(accounts.bob);
+            ordum.invite_member(None,accounts.alice,MemberRole::Regular,None).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.decline_invitation(accounts.bob).unwrap();
+            assert!(ordum.get_invitations().unwrap().is_empty());
+            assert_eq!(ordum.accept_invitation(accounts.bob),Err(Error::InvitationNotFound));
+            assert_eq!(ordum.decline_invitation(accounts.bob),Err(Error::InvitationNotFound));
+            assert_eq!(ordum.team_applicant_profile.get(accounts.bob).unwrap().member_role(accounts.alice),None);
+        }
+
+        #[ink::test]
+        fn members_leave_or_are_removed_but_not_the_last_admin() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            let join = |ordum: &mut OrdumState| {
+                set_caller(accounts.bob);
+                ordum.invite_member(None,accounts.alice,MemberRole::Regular,None).unwrap();
+                set_caller(accounts.alice);
+                ordum.accept_invitation(accounts.bob).unwrap();
+            };
+
+            set_caller(accounts.bob);
+            assert_eq!(ordum.leave_team(accounts.bob),Err(Error::LastAdmin));
+
+            // Regular members can't remove, admins can
+            join(&mut ordum);
+            assert_eq!(ordum.remove_member(Some(accounts.bob),accounts.bob),Err(Error::NotAuthorized));
+            set_caller(accounts.bob);
+            ordum.remove_member(None,accounts.alice).unwrap();
+            assert_eq!(ordum.team_applicant_profile.get(accounts.bob).unwrap().member_role(accounts.alice),None);
+            assert!(ordum.individual_profile.get(accounts.alice).unwrap().ref_team.is_empty());
+
+            join(&mut ordum);
+            ordum.leave_team(accounts.bob).unwrap();
+            assert!(ordum.individual_profile.get(accounts.alice).unwrap().ref_team.is_empty());
+            assert_eq!(ordum.leave_team(accounts.bob),Err(Error::AccountDontExists));
+
+            // The key admin hands over before leaving, other admins leave freely
+            set_caller(accounts.bob);
+            ordum.update_keys(None,accounts.charlie,KeyAction::ADD).unwrap();
+            assert_eq!(ordum.leave_team(accounts.bob),Err(Error::AdminHandoverRequired));
+            set_caller(accounts.charlie);
+            ordum.leave_team(accounts.bob).unwrap();
+            assert_eq!(ordum.key_wallets.get(accounts.bob).unwrap().allowed_keys,vec![accounts.bob]);
+            set_caller(accounts.bob);
+            assert_eq!(ordum.leave_team(accounts.bob),Err(Error::LastAdmin));
+        }
+
+        #[ink::test]
+        fn weakening_recovery_waits_out_the_handover_delay() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            let guarded = RecoveryConfig{ guardians: vec![accounts.charlie], threshold: 1, ..Default::default() };
+
+            set_caller(accounts.bob);
+            ordum.set_recovery_config(None,guarded.clone()).unwrap();
+            // Dropping the guardian and the veto window is delayed
+            ordum.set_recovery_config(None,RecoveryConfig{ handover_delay: 0, ..Default::default() }).unwrap();
+            assert_eq!(ordum.recovery_configs.get(accounts.bob).unwrap().guardians,vec![accounts.charlie]);
+            assert_eq!(ordum.execute_delayed_operation(accounts.bob,1),Err(Error::TimeLocked));
+
+            set_caller(accounts.charlie);
+            ordum.veto_delayed_operation(accounts.bob,1).unwrap();
+            ordum.initiate_recovery(accounts.bob,accounts.django).unwrap();
+            assert_eq!(ordum.initiate_recovery(accounts.bob,accounts.eve),Err(Error::RecoveryPending));
+
+            // The admin being recovered against can't cancel
+            set_caller(accounts.bob);
+            assert_eq!(ordum.cancel_recovery(accounts.bob),Err(Error::NotAuthorized));
+
+            set_caller(accounts.charlie);
+            ordum.execute_recovery(accounts.bob).unwrap();
+            assert_eq!(ordum.key_wallets.get(accounts.bob).unwrap().admin,accounts.django);
+        }
+
+        #[ink::test]
+        fn removed_keys_can_not_veto_and_recovery_swaps_the_admin_key() {
+            let accounts = accounts();
+            let mut ordum = setup();
+
+            set_caller(accounts.bob);
+            ordum.update_keys(None,accounts.charlie,KeyAction::ADD).unwrap();
+            ordum.set_max_keys(None,2).unwrap();
+            ordum.set_recovery_config(None,RecoveryConfig{ guardians: vec![accounts.eve], threshold: 1, ..Default::default() }).unwrap();
+            ordum.update_keys(None,accounts.charlie,KeyAction::REMOVE).unwrap();
+            ordum.update_keys(None,accounts.django,KeyAction::ChangeAdmin).unwrap();
+
+            // Charlie can veto neither its own removal nor the handover while on its way out
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.veto_delayed_operation(accounts.bob,1),Err(Error::NotAuthorized));
+            assert_eq!(ordum.veto_admin_handover(Some(accounts.bob)),Err(Error::NotAuthorized));
+
+            // The full team gets its lost admin key replaced
+            set_caller(accounts.eve);
+            ordum.initiate_recovery(accounts.bob,accounts.frank).unwrap();
+            ordum.execute_recovery(accounts.bob).unwrap();
+            let wallet = ordum.key_wallets.get(accounts.bob).unwrap();
+            assert_eq!(wallet.admin,accounts.frank);
+            assert_eq!(wallet.allowed_keys,vec![accounts.frank,accounts.charlie]);
+            assert!(ordum.key_index.get(accounts.bob).is_none());
+            assert_eq!(ordum.key_index.get(accounts.frank),Some(vec![accounts.bob]));
+        }
+
+        #[ink::test]
+        fn legacy_proposals_are_migrated() {
+            let accounts = accounts();
+            let mut ordum = setup();
+
+            let legacy = LegacyProject{
+                id: 1,
+                total_mem: 4,
+                ..Default::default()
+            };
+            ordum.proposal.insert(accounts.alice,&vec![legacy]);
+
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.migrate_proposals(vec![accounts.alice]),Err(Error::NotAuthorized));
+
+            // New proposals are numbered after the legacy ones
+            set_caller(accounts.alice);
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-2".into(),0).unwrap();
+            ordum.migrate_proposals(vec![accounts.alice]).unwrap();
+            let projects = ordum.projects.get(accounts.alice).unwrap();
+            assert_eq!(projects.iter().map(|project| project.id).collect::<Vec<_>>(),vec![1,2]);
+            assert_eq!(projects[0].status,Status::Draft);
+            assert!(!ordum.proposal.contains(accounts.alice));
+        }
+
+        #[ink::test]
+        fn individual_proposals_are_persisted() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),10).unwrap();
+            ordum.add_proposal(None,Chains::Kusama,Some(7),"cid-2".into(),10).unwrap();
+            ordum.add_proposal(None,Chains::OffChain,None,"cid-3".into(),10).unwrap();
+
+            assert_eq!(ordum.fetch_latest_proposal_id(None),Ok(3));
+            let second = ordum.fetch_proposal(None,2).unwrap();
+            assert_eq!(second.data.file,"cid-2");
+            assert_eq!(second.data.referenda_no,Some(7));
+            assert_eq!(ordum.individual_profile.get(accounts.alice).unwrap().applications,3);
+            assert!(matches!(ordum.fetch_proposal(None,0),Err(MilestoneError::ProjectNotFound)));
+        }
+
+        #[ink::test]
+        fn proposal_ids_do_not_wrap() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            (0..u8::MAX).for_each(|_| ordum.add_proposal(None,Chains::Polkadot,None,"cid".into(),0).unwrap());
+            assert_eq!(ordum.fetch_latest_proposal_id(None),Ok(u8::MAX));
+            assert_eq!(ordum.add_proposal(None,Chains::Polkadot,None,"cid".into(),0),Err(MilestoneError::MaxProjectsExceeded));
+        }
+
+        #[ink::test]
+        fn team_proposals_are_persisted() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.bob);
+
+            ordum.add_proposal(None,Chains::Kusama,None,"team-1".into(),10).unwrap();
+            ordum.add_proposal(Some(accounts.bob),Chains::Kusama,None,"team-2".into(),10).unwrap();
+
+            assert_eq!(ordum.fetch_latest_proposal_id(None),Ok(2));
+            assert_eq!(ordum.fetch_proposal(None,1).unwrap().data.file,"team-1");
+            assert_eq!(ordum.team_applicant_profile.get(accounts.bob).unwrap().applications,2);
+        }
+
+        #[ink::test]
+        fn failed_team_operations_keep_their_error() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.bob);
+            ordum.update_keys(None,accounts.charlie,KeyAction::ADD).unwrap();
+            ordum.set_threshold(None,2).unwrap();
+
+            // Pivoting a proposal the team doesn't have
+            ordum.pivote_milestone(None,9,1,"New scope".into(),"p1".into(),5,MilestoneTerms::default()).unwrap();
+            let id = ordum.get_pending_operations(accounts.bob).unwrap()[0].id;
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.approve_operation(Some(accounts.bob),id),Err(Error::Milestone(MilestoneError::ProjectNotFound)));
+        }
+
+        #[ink::test]
+        fn milestones_and_edits_are_persisted() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),10).unwrap();
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-2".into(),10).unwrap();
+
+            ordum.add_milestone(None,2,"m1".into(),5,MilestoneTerms::default()).unwrap();
+            ordum.add_milestone(None,2,"m2".into(),5,MilestoneTerms::default()).unwrap();
+            ordum.add_milestone(None,2,"m3".into(),5,MilestoneTerms::default()).unwrap();
+            ordum.edit_milestone(None,2,1,"m1-e1".into(),5).unwrap();
+            ordum.edit_milestone(None,2,3,"m3-e1".into(),5).unwrap();
+
+            let fetched = ordum.fetch_milestone(None,2,None).unwrap();
+            let main = fetched.main.unwrap();
+            assert_eq!(main.len(),3);
+            assert_eq!(main.iter().map(|mile| mile.main_index).collect::<Vec<_>>(),vec![1,2,3]);
+            assert_eq!(main[1].data,"m2");
+            assert_eq!(fetched.all_edits.unwrap().len(),2);
+
+            let first = ordum.fetch_milestone(None,2,Some(1)).unwrap();
+            assert_eq!(first.edited_per_mile.unwrap()[0].data,"m1-e1");
+
+            // The other proposal is left untouched
+            assert_eq!(ordum.fetch_milestone(None,1,None).unwrap_err(),MilestoneError::MilestoneNotFound);
+        }
+
+        #[ink::test]
+        fn repeated_edits_are_kept_once_per_milestone() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),10).unwrap();
+            ordum.add_milestone(None,1,"m1".into(),5,MilestoneTerms::default()).unwrap();
+            ordum.add_milestone(None,1,"m2".into(),5,MilestoneTerms::default()).unwrap();
+            ordum.edit_milestone(None,1,1,"m1-e1".into(),5).unwrap();
+            ordum.edit_milestone(None,1,1,"m1-e2".into(),5).unwrap();
+            ordum.edit_milestone(None,1,1,"m1-e3".into(),5).unwrap();
+            ordum.edit_milestone(None,1,2,"m2-e1".into(),5).unwrap();
+
+            let first = ordum.fetch_milestone(None,1,Some(1)).unwrap();
+            let history = first.edited_per_mile.unwrap();
+            assert_eq!(history.iter().map(|edit| edit.edited_index).collect::<Vec<_>>(),vec![1,2,3]);
+            assert_eq!(history[2].data,"m1-e3");
+            assert_eq!(first.main.unwrap()[0].no_edits,3);
+
+            let all_edits = ordum.fetch_milestone(None,1,None).unwrap().all_edits.unwrap();
+            assert_eq!(all_edits.len(),2);
+            assert_eq!(all_edits[&1].len(),3);
+            assert_eq!(all_edits[&2].len(),1);
+
+            assert_eq!(ordum.edit_milestone(None,1,3,"m3-e1".into(),5),Err(MilestoneError::MilestoneNotFound));
+
+            // Edit numbers stop at 255 instead of wrapping
+            (1..u8::MAX).for_each(|_| ordum.edit_milestone(None,1,2,"m2-e".into(),5).unwrap());
+            assert_eq!(ordum.edit_milestone(None,1,2,"m2-e".into(),5),Err(MilestoneError::MaxEditsExceeded));
+        }
+
+        #[ink::test]
+        fn milestone_numbers_do_not_wrap() {
+            let mut ordum = setup();
+            set_caller(accounts().alice);
+
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),0).unwrap();
+            (0..u8::MAX).for_each(|_| ordum.add_milestone(None,1,String::new(),1,MilestoneTerms::default()).unwrap());
+            assert_eq!(ordum.add_milestone(None,1,String::new(),1,MilestoneTerms::default()),Err(MilestoneError::MaxMilestonesExceeded));
+        }
+
+        #[ink::test]
+        fn storage_usage_is_accounted_and_refunded() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),100).unwrap();
+            ordum.add_milestone(None,1,"m1".into(),1_000,MilestoneTerms::default()).unwrap();
+            ordum.add_milestone(None,1,"m2".into(),2_000,MilestoneTerms::default()).unwrap();
+            assert_eq!(ordum.get_storage_usage(None,1),Ok((3_100,MAX_MEM)));
+
+            // The edit replaces the milestone's content
+            ordum.edit_milestone(None,1,1,"m1-e1".into(),1_500).unwrap();
+            assert_eq!(ordum.get_storage_usage(None,1),Ok((3_600,MAX_MEM)));
+
+            // The pivot replaces the second milestone
+            ordum.pivote_milestone(None,1,2,"New scope".into(),"p2".into(),500,MilestoneTerms::default()).unwrap();
+            assert_eq!(ordum.get_storage_usage(None,1),Ok((2_100,MAX_MEM)));
+
+            // The replaced milestone can not be edited anymore
+            assert_eq!(ordum.edit_milestone(None,1,2,"m2-e1".into(),1),Err(MilestoneError::MilestoneReplaced));
+            assert_eq!(ordum.get_storage_usage(None,1),Ok((2_100,MAX_MEM)));
+        }
+
+        #[ink::test]
+        fn storage_quota_is_enforced() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),0).unwrap();
+            ordum.add_milestone(None,1,"m1".into(),MAX_MEM,MilestoneTerms::default()).unwrap();
+            assert_eq!(ordum.add_milestone(None,1,"m2".into(),1,MilestoneTerms::default()),Err(MilestoneError::StorageExceeded));
+
+            // Replacing the content frees its quota
+            ordum.edit_milestone(None,1,1,"m1-e1".into(),MAX_MEM - 1).unwrap();
+            ordum.add_milestone(None,1,"m2".into(),1,MilestoneTerms::default()).unwrap();
+
+            // Program quotas can not go below the usage of the attached projects
+            set_caller(accounts.charlie);
+            ordum.create_issuer_profile("Treasury".into(),None,"Ecosystem grants".into(),vec![Chains::Polkadot],vec![Categories::Defi],vec![],vec![],vec![]).unwrap();
+            ordum.create_program(1,"Defi".into(),0,100,50,vec![Categories::Defi],vec![Chains::Polkadot],50,"template".into()).unwrap();
+            set_caller(accounts.alice);
+            ordum.attach_proposal(None,1,1,10).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.set_program_quota(1,10),Err(Error::QuotaBelowUsage));
+
+            // Only the owner changes the default quota
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.set_default_quota(10),Err(Error::NotAuthorized));
+            set_caller(accounts.alice);
+            ordum.set_default_quota(10).unwrap();
+            assert_eq!(ordum.add_proposal(None,Chains::Polkadot,None,"cid-2".into(),11),Err(MilestoneError::StorageExceeded));
+        }
+
+        #[ink::test]
+        fn overdue_milestones_follow_deadlines_and_deliverables() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.alice);
+
+            let terms = |due:Timestamp,deliverables:Vec<&str>| MilestoneTerms{
+                due,
+                amount: 100,
+                deliverables: deliverables.into_iter().map(String::from).collect()
+            };
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),10).unwrap();
+            ordum.add_milestone(None,1,"m1".into(),5,terms(1_000,vec!["spec","code"])).unwrap();
+            ordum.add_milestone(None,1,"m2".into(),5,terms(0,vec!["docs"])).unwrap();
+            ordum.add_milestone(None,1,"m3".into(),5,terms(1_500,vec!["audit"])).unwrap();
+            ordum.pivote_milestone(None,1,3,"Audit moved".into(),"p3".into(),5,terms(1_800,vec!["tests"])).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            let overdue = ordum.get_overdue_milestones(None,Some(1)).unwrap();
+            assert_eq!(overdue.iter().map(|(_,mile)| mile.due).collect::<Vec<_>>(),vec![1_000,1_800]);
+
+            ordum.update_deliverable(None,1,1,0,DeliverableStatus::Done).unwrap();
+            assert_eq!(ordum.get_overdue_milestones(None,None).unwrap().len(),2);
+            ordum.update_deliverable(None,1,1,1,DeliverableStatus::Done).unwrap();
+            // The pivoted milestone is the one on the current path
+            ordum.update_deliverable(None,1,3,0,DeliverableStatus::Done).unwrap();
+            assert!(ordum.get_overdue_milestones(None,None).unwrap().is_empty());
+
+            assert_eq!(ordum.update_deliverable(None,1,2,1,DeliverableStatus::Done),Err(MilestoneError::DeliverableNotFound));
+
+            // Late milestones are reported as soon as they are added
+            ordum.add_milestone(None,1,"m4".into(),5,terms(1_900,vec![])).unwrap();
+            ordum.edit_milestone(None,1,1,"m1-e1".into(),5).unwrap();
+            let reported: Vec<(u8,Timestamp)> = emitted().into_iter().filter_map(|event|{
+                match event {
+                    Event::MilestoneOverdue(overdue) => Some((overdue.mile_no,overdue.due)),
+                    _ => None
+                }
+            }).collect();
+            // The first milestone was reported while it still had a pending deliverable
+            assert_eq!(reported,vec![(1,1_000),(4,1_900)]);
+        }
+
+        /// Alice's first proposal with one milestone, applied to charlie's issuer and approved
+        fn approved_proposal(ordum: &mut OrdumState) {
+            let accounts = accounts();
+            set_caller(accounts.charlie);
+            ordum.create_issuer_profile(
+                "Treasury".into(),
+                None,
+                "Ecosystem grants".into(),
+                vec![Chains::Polkadot],
+                vec![Categories::Defi],
+                vec![],
+                vec![],
+                vec![]
+            ).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),10).unwrap();
+            ordum.add_milestone(None,1,"m1".into(),5,MilestoneTerms::default()).unwrap();
+            assert_eq!(ordum.submit_milestone_delivery(None,1,1,"evidence".into()),Err(MilestoneError::NotApproved));
+            ordum.apply_grant(None,1,1,"application".into()).unwrap();
+
+            set_caller(accounts.charlie);
+            ordum.review_application(1,1,Status::UnderReview,None).unwrap();
+            ordum.review_application(1,1,Status::Approved,None).unwrap();
+        }
+
+        #[ink::test]
+        fn conflicts_are_declared_by_assigned_reviewers() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.charlie);
+            ordum.create_issuer_profile("Treasury".into(),None,"Ecosystem grants".into(),vec![Chains::Polkadot],vec![Categories::Defi],vec![],vec![],vec![]).unwrap();
+            ordum.set_rubric(1,vec![Criterion{ name: "Impact".into(), weight: 1 }]).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),0).unwrap();
+            ordum.apply_grant(None,1,1,"application".into()).unwrap();
+
+            set_caller(accounts.charlie);
+            ordum.assign_reviewers(1,1,vec![accounts.django]).unwrap();
+
+            set_caller(accounts.eve);
+            assert_eq!(ordum.declare_conflict(1,1,"spam".into()),Err(Error::NotReviewer));
+
+            set_caller(accounts.django);
+            assert_eq!(ordum.declare_conflict(1,1,"x".repeat(MAX_REASON_LEN as usize + 1)),Err(Error::ReasonTooLong));
+            ordum.declare_conflict(1,1,"advisor".into()).unwrap();
+            ordum.declare_conflict(1,1,"former advisor".into()).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.get_conflicts(1,1).unwrap().len(),1);
+        }
+
+        #[ink::test]
+        fn programs_check_the_category_and_budget() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            approved_proposal(&mut ordum);
+
+            set_caller(accounts.charlie);
+            ordum.create_program(1,"Defi".into(),0,100,50,vec![Categories::Defi],vec![Chains::Polkadot,Chains::Kusama],80,"template".into()).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-2".into(),0).unwrap();
+            assert_eq!(ordum.attach_proposal(None,2,1,60),Err(Error::NotEligible));
+            ordum.attach_proposal(None,2,1,50).unwrap();
+
+            // Bob's team is registered for infrastructure only
+            set_caller(accounts.bob);
+            ordum.add_proposal(None,Chains::Kusama,None,"cid-3".into(),0).unwrap();
+            assert_eq!(ordum.attach_proposal(None,1,1,10),Err(Error::NotEligible));
+        }
+
+        #[ink::test]
+        fn issuer_keys_are_managed_by_the_admin() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            approved_proposal(&mut ordum);
+
+            // Only the admin updates the keys
+            set_caller(accounts.alice);
+            assert_eq!(ordum.update_issuer_keys(1,accounts.alice,KeyAction::ADD),Err(Error::NotAuthorized));
+            set_caller(accounts.charlie);
+            ordum.update_issuer_keys(1,accounts.django,KeyAction::ADD).unwrap();
+            assert_eq!(ordum.update_issuer_keys(1,accounts.eve,KeyAction::ChangeAdmin),Err(Error::AccountDontExists));
+            assert_eq!(ordum.update_issuer_keys(1,accounts.charlie,KeyAction::REMOVE),Err(Error::NotAuthorized));
+
+            // Rotating the admin to a new key
+            ordum.update_issuer_keys(1,accounts.django,KeyAction::ChangeAdmin).unwrap();
+            set_caller(accounts.django);
+            ordum.update_issuer_keys(1,accounts.charlie,KeyAction::REMOVE).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.review_application(1,1,Status::Completed,None),Err(Error::NotAuthorized));
+        }
+
+        #[ink::test]
+        fn milestone_delivery_is_signed_off_by_the_issuer() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            approved_proposal(&mut ordum);
+
+            set_caller(accounts.alice);
+            ordum.submit_milestone_delivery(None,1,1,"evidence-1".into()).unwrap();
+            assert_eq!(ordum.submit_milestone_delivery(None,1,1,"evidence-2".into()),Err(MilestoneError::InvalidState));
+            assert_eq!(ordum.edit_milestone(None,1,1,"m1-e1".into(),5),Err(MilestoneError::InvalidState));
+            // Applicants can't evaluate their own delivery
+            assert_eq!(ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::Accepted,None),Err(MilestoneError::NotAuthorized));
+
+            set_caller(accounts.charlie);
+            ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::ChangesRequested,Some("comments".into())).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.submit_milestone_delivery(None,1,1,"evidence-2".into()).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::Planned,None),Err(MilestoneError::InvalidState));
+            ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::Accepted,None).unwrap();
+
+            set_caller(accounts.alice);
+            let milestone = ordum.fetch_milestone(None,1,Some(1)).unwrap().main.unwrap().remove(0);
+            assert_eq!(milestone.state,MilestoneState::Accepted);
+            assert_eq!(milestone.evidence,Some("evidence-2".into()));
+        }
+
+        #[ink::test]
+        fn applications_move_through_their_lifecycle() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            let status = |ordum: &OrdumState,id| {
+                set_caller(accounts.alice);
+                ordum.get_application(1,id).unwrap().status
+            };
+            set_caller(accounts.charlie);
+            ordum.create_issuer_profile("Treasury".into(),None,"Ecosystem grants".into(),vec![Chains::Polkadot],vec![Categories::Defi],vec![],vec![],vec![]).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),0).unwrap();
+            ordum.apply_grant(None,1,1,"application".into()).unwrap();
+            assert_eq!(status(&ordum,1),Status::Submitted);
+            assert_eq!(ordum.apply_grant(None,1,1,"again".into()),Err(Error::InvalidTransition));
+            assert_eq!(ordum.review_application(1,1,Status::UnderReview,None),Err(Error::NotAuthorized));
+
+            // Changes are requested and submitted again
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.review_application(1,1,Status::Approved,None),Err(Error::InvalidTransition));
+            ordum.review_application(1,1,Status::UnderReview,None).unwrap();
+            ordum.review_application(1,1,Status::ChangesRequested,Some("budget".into())).unwrap();
+            assert_eq!(status(&ordum,1),Status::ChangesRequested);
+            ordum.update_application(1,1,"application-2".into()).unwrap();
+            assert_eq!(status(&ordum,1),Status::Submitted);
+
+            // A rejected proposal applies again and is abandoned
+            set_caller(accounts.charlie);
+            ordum.review_application(1,1,Status::UnderReview,None).unwrap();
+            ordum.review_application(1,1,Status::Rejected,None).unwrap();
+            set_caller(accounts.alice);
+            assert_eq!(ordum.update_application(1,1,"application-3".into()),Err(Error::InvalidTransition));
+            ordum.apply_grant(None,1,1,"application-3".into()).unwrap();
+            ordum.abandon_proposal(None,1).unwrap();
+            assert_eq!(status(&ordum,2),Status::Abandoned);
+            assert_eq!(status(&ordum,1),Status::Rejected);
+            assert_eq!(ordum.abandon_proposal(None,1),Err(Error::InvalidTransition));
+        }
+
+        #[ink::test]
+        fn conflicted_reviewers_can_not_evaluate_milestones() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.charlie);
+            ordum.create_issuer_profile("Treasury".into(),None,"Ecosystem grants".into(),vec![Chains::Polkadot],vec![Categories::Defi],vec![],vec![],vec![]).unwrap();
+            ordum.set_rubric(1,vec![Criterion{ name: "Impact".into(), weight: 1 }]).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),0).unwrap();
+            let terms = MilestoneTerms{ deliverables: vec!["spec".into()], ..Default::default() };
+            ordum.add_milestone(None,1,"m1".into(),5,terms).unwrap();
+            ordum.apply_grant(None,1,1,"application".into()).unwrap();
+
+            set_caller(accounts.charlie);
+            ordum.assign_reviewers(1,1,vec![accounts.django,accounts.eve]).unwrap();
+            set_caller(accounts.django);
+            ordum.declare_conflict(1,1,"advisor".into()).unwrap();
+            set_caller(accounts.charlie);
+            ordum.review_application(1,1,Status::Approved,None).unwrap();
+
+            set_caller(accounts.alice);
+            ordum.submit_milestone_delivery(None,1,1,"evidence".into()).unwrap();
+            set_caller(accounts.django);
+            assert_eq!(ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::ChangesRequested,None),Err(MilestoneError::NotAuthorized));
+
+            // Reviewers only request changes, the issuer accepts
+            set_caller(accounts.eve);
+            assert_eq!(ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::Accepted,None),Err(MilestoneError::NotAuthorized));
+            ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::ChangesRequested,Some("tests".into())).unwrap();
+            set_caller(accounts.alice);
+            ordum.submit_milestone_delivery(None,1,1,"evidence-2".into()).unwrap();
+            set_caller(accounts.charlie);
+            ordum.evaluate_milestone(accounts.alice,1,1,MilestoneState::Accepted,None).unwrap();
+
+            // Accepted deliveries are final
+            set_caller(accounts.alice);
+            assert_eq!(ordum.update_deliverable(None,1,1,0,DeliverableStatus::Pending),Err(MilestoneError::InvalidState));
+        }
+
+        #[ink::test]
+        fn escrow_pays_accepted_milestones_and_is_reclaimed_after_abandon() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            let contract = test::callee::<DefaultEnvironment>();
+            let balance = |account| test::get_account_balance::<DefaultEnvironment>(account).unwrap();
+
+            // The proposal is approved in a program with an ask of 120
+            set_caller(accounts.charlie);
+            ordum.create_issuer_profile("Treasury".into(),None,"Ecosystem grants".into(),vec![Chains::Polkadot],vec![Categories::Defi],vec![],vec![],vec![]).unwrap();
+            ordum.create_program(1,"Defi".into(),0,100,500,vec![Categories::Defi],vec![Chains::Polkadot],200,"template".into()).unwrap();
+            set_caller(accounts.alice);
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-1".into(),10).unwrap();
+            ordum.add_milestone(None,1,"m1".into(),5,MilestoneTerms::default()).unwrap();
+            ordum.attach_proposal(None,1,1,120).unwrap();
+            ordum.apply_grant(None,1,1,"application".into()).unwrap();
+            set_caller(accounts.charlie);
+            ordum.review_application(1,1,Status::UnderReview,None).unwrap();
+            ordum.review_application(1,1,Status::Approved,None).unwrap();
+
+            set_caller(accounts.alice);
+            let terms = MilestoneTerms{ amount: 100, ..Default::default() };
+            ordum.add_milestone(None,1,"m2".into(),5,terms).unwrap();
+            assert_eq!(ordum.deposit_escrow(accounts.alice,1),Err(Error::InvalidAmount));
+            test::set_value_transferred::<DefaultEnvironment>(150);
+            assert_eq!(ordum.deposit_escrow(accounts.alice,1),Err(Error::NotAuthorized));
+
+            set_caller(accounts.charlie);
+            test::set_account_balance::<DefaultEnvironment>(contract,150);
+            ordum.deposit_escrow(accounts.alice,1).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            assert_eq!(ordum.projects.get(accounts.alice).unwrap()[0].status,Status::Funded);
+            assert_eq!(ordum.reclaim_escrow(accounts.alice,1),Err(Error::NotCancelled));
+
+            set_caller(accounts.alice);
+            ordum.submit_milestone_delivery(None,1,2,"evidence".into()).unwrap();
+            let before = balance(accounts.alice);
+            set_caller(accounts.charlie);
+            ordum.evaluate_milestone(accounts.alice,1,2,MilestoneState::Accepted,None).unwrap();
+            assert_eq!(balance(accounts.alice),before + 100);
+            assert_eq!(ordum.get_escrow(accounts.alice,1).unwrap().remaining(),50);
+
+            // Paid milestones can not be pivoted away and paid again
+            set_caller(accounts.alice);
+            let terms = MilestoneTerms{ amount: 50, ..Default::default() };
+            assert_eq!(ordum.pivote_milestone(None,1,2,"Again".into(),"p2".into(),5,terms.clone()),Err(MilestoneError::InvalidState));
+
+            // Payouts of program projects stay within the approved ask
+            ordum.add_milestone(None,1,"m3".into(),5,terms).unwrap();
+            ordum.submit_milestone_delivery(None,1,3,"evidence".into()).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(ordum.evaluate_milestone(accounts.alice,1,3,MilestoneState::Accepted,None),Err(MilestoneError::AskExceeded));
+
+            set_caller(accounts.alice);
+            ordum.abandon_proposal(None,1).unwrap();
+            let before = balance(accounts.charlie);
+            set_caller(accounts.charlie);
+            ordum.reclaim_escrow(accounts.alice,1).unwrap();
+            assert_eq!(balance(accounts.charlie),before + 50);
+            assert_eq!(ordum.reclaim_escrow(accounts.alice,1),Err(Error::EscrowNotFound));
+        }
+
+        #[ink::test]
+        fn escrow_stays_with_its_issuer_until_reclaimed() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            approved_proposal(&mut ordum);
+            let contract = test::callee::<DefaultEnvironment>();
+            test::set_account_balance::<DefaultEnvironment>(contract,200);
+
+            set_caller(accounts.charlie);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            ordum.deposit_escrow(accounts.alice,1).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            ordum.review_application(1,1,Status::Rejected,None).unwrap();
+
+            // The rejected proposal can't move to another issuer with the funds
+            set_caller(accounts.django);
+            ordum.create_issuer_profile("Foundation".into(),None,"Research grants".into(),vec![Chains::Polkadot],vec![Categories::Defi],vec![],vec![],vec![]).unwrap();
+            set_caller(accounts.alice);
+            assert_eq!(ordum.apply_grant(None,2,1,"application".into()),Err(Error::EscrowNotSettled));
+            set_caller(accounts.charlie);
+            ordum.reclaim_escrow(accounts.alice,1).unwrap();
+            set_caller(accounts.alice);
+            ordum.apply_grant(None,2,1,"application".into()).unwrap();
+
+            // Completed grants give back what was not paid out
+            ordum.add_proposal(None,Chains::Polkadot,None,"cid-2".into(),10).unwrap();
+            ordum.apply_grant(None,1,2,"application".into()).unwrap();
+            set_caller(accounts.charlie);
+            ordum.review_application(1,2,Status::UnderReview,None).unwrap();
+            ordum.review_application(1,2,Status::Approved,None).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            ordum.deposit_escrow(accounts.alice,2).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            ordum.review_application(1,2,Status::Completed,None).unwrap();
+            ordum.reclaim_escrow(accounts.alice,2).unwrap();
+            assert_eq!(ordum.get_escrow(accounts.alice,2).unwrap().reclaimed,100);
+        }
+
+        #[ink::test]
+        fn milestone_writes_require_a_profile() {
+            let accounts = accounts();
+            let mut ordum = setup();
+            set_caller(accounts.charlie);
+
+            assert_eq!(ordum.add_proposal(None,Chains::Polkadot,None,"cid".into(),10),Err(MilestoneError::NotAuthorized));
+            assert_eq!(ordum.add_milestone(None,1,"m1".into(),5,MilestoneTerms::default()),Err(MilestoneError::NotAuthorized));
+            assert_eq!(ordum.add_milestone(Some(accounts.bob),1,"m1".into(),5,MilestoneTerms::default()),Err(MilestoneError::NotAuthorized));
+        }
+    }
+
     //  ---------------------------------------- E2E TESTING -------------------------------------------------- //
 
     #[cfg(all(test, feature = "e2e-tests"))]